@@ -0,0 +1,175 @@
+//! Solver benchmarks, run with `cargo bench`.
+//!
+//! Whole-puzzle solves go through every engine, and each strategy is also
+//! measured on its own against a puzzle that inference alone gets stuck on.
+//! Backtracking compares the copies the search keeps with a trail of changes.
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use sudoku_solver::{
+    Backend, SolverConfig, Sudoku, SudokuPossibilities,
+    strategy::{Strategy, Technique},
+};
+
+/// Puzzles of increasing difficulty
+const CORPUS: [(&str, &str); 4] = [
+    ("easy", include_str!("../puzzles/wiki.txt")),
+    ("medium", include_str!("../puzzles/symmetric.txt")),
+    ("hard", include_str!("../puzzles/7gJb9G8fRt.txt")),
+    // Built so that guessing cells and values in order backtracks a lot
+    (
+        "pathological",
+        ".........
+.....3.85
+..1.2....
+...5.7...
+..4...1..
+.9.......
+5......73
+..2.1....
+....4...9",
+    ),
+];
+
+fn corpus() -> impl Iterator<Item = (&'static str, Sudoku)> {
+    CORPUS
+        .iter()
+        .map(|&(name, data)| (name, Sudoku::parse(data).expect("Invalid puzzle")))
+}
+
+fn engines() -> Vec<(&'static str, SolverConfig)> {
+    vec![
+        ("propagation", SolverConfig::default()),
+        ("basic", SolverConfig::basic()),
+        (
+            "sequential",
+            SolverConfig {
+                parallel_depth: 0,
+                ..SolverConfig::default()
+            },
+        ),
+        (
+            "dlx",
+            SolverConfig {
+                backend: Backend::Dlx,
+                ..SolverConfig::default()
+            },
+        ),
+        #[cfg(feature = "sat")]
+        (
+            "sat",
+            SolverConfig {
+                backend: Backend::Sat,
+                ..SolverConfig::default()
+            },
+        ),
+    ]
+}
+
+fn solve(c: &mut Criterion) {
+    for (name, sudoku) in corpus() {
+        let mut group = c.benchmark_group(format!("solve/{name}"));
+        for (engine, config) in engines() {
+            let solver = config.solver();
+            group.bench_function(engine, |b| {
+                b.iter(|| solver.solve(black_box(&sudoku)).unwrap())
+            });
+        }
+        group.finish();
+    }
+}
+
+fn strategies(c: &mut Criterion) {
+    let sudoku = Sudoku::parse(CORPUS[2].1).unwrap();
+    let mut state = SudokuPossibilities::from(sudoku);
+    state.propagate().unwrap();
+
+    let techniques = [
+        Technique::HiddenSingles,
+        Technique::Pointing,
+        Technique::BoxLineReduction,
+        Technique::NakedPairs,
+        Technique::HiddenPairs,
+        Technique::NakedTriples,
+        Technique::HiddenTriples,
+        Technique::XWing,
+        Technique::Swordfish,
+        Technique::XyWing,
+        Technique::SimpleColoring,
+        Technique::XChains(6),
+        Technique::XyChains(6),
+        Technique::AlsXz,
+        Technique::ForcingChains(4),
+    ];
+    let mut group = c.benchmark_group("strategy");
+    for technique in techniques {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{technique:?}")),
+            &state,
+            |b, &state| {
+                b.iter(|| {
+                    let mut sp = state;
+                    technique.apply(black_box(&mut sp))
+                })
+            },
+        );
+    }
+    group.finish();
+
+    c.bench_function("propagate", |b| {
+        b.iter(|| {
+            let mut sp = SudokuPossibilities::from(black_box(sudoku));
+            sp.propagate()
+        })
+    });
+}
+
+/// Undoing a guess by restoring a copy of the grid, as the search does,
+/// against undoing a trail of the cells it changed
+fn backtrack(c: &mut Criterion) {
+    let config = SolverConfig::basic();
+    let mut before = SudokuPossibilities::from(Sudoku::parse(CORPUS[2].1).unwrap());
+    before.infer(&config).unwrap();
+    let (r, c0) = (0..81)
+        .map(|i| (i / 9, i % 9))
+        .find(|&(r, c)| before.grid[r][c].count() > 1)
+        .unwrap();
+    let mut after = before;
+    after.place((r, c0), before.grid[r][c0].iter().next().unwrap());
+    let _ = after.infer(&config);
+    let changed: Vec<_> = (0..81)
+        .map(|i| (i / 9, i % 9))
+        .filter(|&(r, c)| after.grid[r][c] != before.grid[r][c])
+        .collect();
+
+    let mut group = c.benchmark_group(format!("backtrack/{}", changed.len()));
+    group.bench_function("copy", |b| {
+        let (mut sp, mut stack) = (before, Vec::new());
+        b.iter(|| {
+            stack.push(sp);
+            for &(r, c) in &changed {
+                sp.grid[r][c] = after.grid[r][c];
+            }
+            black_box(&mut sp);
+            sp = stack.pop().unwrap();
+        })
+    });
+    group.bench_function("trail", |b| {
+        let (mut sp, mut trail) = (before, Vec::new());
+        b.iter(|| {
+            for &(r, c) in &changed {
+                trail.push(((r, c), sp.grid[r][c]));
+                sp.grid[r][c] = after.grid[r][c];
+            }
+            black_box(&mut sp);
+            while let Some(((r, c), old)) = trail.pop() {
+                sp.grid[r][c] = old;
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, solve, strategies, backtrack);
+criterion_main!(benches);
@@ -0,0 +1,138 @@
+use std::sync::mpsc::SyncSender;
+
+#[cfg(feature = "parallel")]
+use rayon::iter::{IntoParallelRefIterator, ParallelBridge, ParallelIterator};
+
+use crate::{SolveError, SolveOutcome, SolverConfig, Sudoku};
+
+/// Solve many puzzles, returning the results in the same order.
+///
+/// With the `parallel` feature the puzzles are spread over the threads of
+/// `config`, and each one is solved on a single thread, which is faster than
+/// splitting up the search of every puzzle when there are many of them.
+pub fn solve_batch(
+    puzzles: &[Sudoku],
+    config: &SolverConfig,
+) -> Vec<Result<SolveOutcome, SolveError>> {
+    let config = SolverConfig {
+        parallel_depth: 0,
+        ..config.clone()
+    };
+    let solver = config.solver();
+    #[cfg(feature = "parallel")]
+    let results = config.parallelism.install(|| {
+        puzzles
+            .par_iter()
+            .map(|sudoku| solver.solve(sudoku))
+            .collect()
+    });
+    #[cfg(not(feature = "parallel"))]
+    let results = puzzles.iter().map(|sudoku| solver.solve(sudoku)).collect();
+    results
+}
+
+/// Solve puzzles as they arrive, sending each result along with the index of
+/// its puzzle as soon as it is ready. Returns once `puzzles` runs out or the
+/// receiving end of `results` hangs up.
+///
+/// Only the puzzles being solved and the results not yet received are held
+/// in memory. Passing a [`Receiver`](std::sync::mpsc::Receiver) from a
+/// [`sync_channel`](std::sync::mpsc::sync_channel) as `puzzles` makes the
+/// producer wait when the solvers fall behind, and a full `results` channel
+/// makes the solvers wait for the consumer. With the `parallel` feature the
+/// puzzles are solved on the threads of `config`, so results may arrive out
+/// of order.
+pub fn solve_stream<I>(
+    puzzles: I,
+    results: SyncSender<(usize, Result<SolveOutcome, SolveError>)>,
+    config: &SolverConfig,
+) where
+    I: IntoIterator<Item = Sudoku>,
+    I::IntoIter: Send,
+{
+    let config = SolverConfig {
+        parallel_depth: 0,
+        ..config.clone()
+    };
+    let solver = config.solver();
+    let puzzles = puzzles.into_iter().enumerate();
+    #[cfg(feature = "parallel")]
+    let _ = config.parallelism.install(|| {
+        puzzles
+            .par_bridge()
+            .try_for_each_with(results, |results, (i, sudoku)| {
+                // Nobody is listening, so there is no point in going on
+                results.send((i, solver.solve(&sudoku))).map_err(drop)
+            })
+    });
+    #[cfg(not(feature = "parallel"))]
+    for (i, sudoku) in puzzles {
+        if results.send((i, solver.solve(&sudoku))).is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Digit;
+    use std::sync::mpsc::sync_channel;
+
+    #[test]
+    fn batch_keeps_order() {
+        let mut puzzles: Vec<Sudoku> = ["wiki", "symmetric", "7gJb9G8fRt"]
+            .iter()
+            .map(|name| {
+                let data = std::fs::read_to_string(format!("puzzles/{name}.txt")).unwrap();
+                Sudoku::parse(&data).unwrap()
+            })
+            .collect();
+        let mut invalid = Sudoku::splat(None);
+        invalid.grid[0][0] = Some(Digit::unchecked(1));
+        invalid.grid[0][1] = Some(Digit::unchecked(1));
+        puzzles.push(invalid);
+
+        let results = solve_batch(&puzzles, &SolverConfig::basic());
+        assert_eq!(results.len(), 4);
+        for (sudoku, result) in puzzles.iter().zip(&results).take(3) {
+            assert_eq!(Some(result.as_ref().unwrap().solution), sudoku.solve());
+        }
+        assert!(matches!(results[3], Err(SolveError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn stream_through_bounded_channels() {
+        let data = std::fs::read_to_string("puzzles/wiki.txt").unwrap();
+        let sudoku = Sudoku::parse(&data).unwrap();
+        let (puzzles_tx, puzzles_rx) = sync_channel(2);
+        let (results_tx, results_rx) = sync_channel(2);
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                for _ in 0..20 {
+                    puzzles_tx.send(sudoku).unwrap();
+                }
+                drop(puzzles_tx);
+            });
+            scope.spawn(|| solve_stream(puzzles_rx, results_tx, &SolverConfig::basic()));
+
+            let mut seen = [false; 20];
+            for (i, result) in results_rx {
+                assert_eq!(Some(result.unwrap().solution), sudoku.solve());
+                seen[i] = true;
+            }
+            assert!(seen.iter().all(|&seen| seen));
+        });
+    }
+
+    #[test]
+    fn stream_stops_when_results_are_dropped() {
+        let (results_tx, results_rx) = sync_channel(0);
+        drop(results_rx);
+        solve_stream(
+            std::iter::repeat(Sudoku::splat(None)),
+            results_tx,
+            &SolverConfig::basic(),
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use std::ops;
+
+use crate::{
+    Digit, SudokuPossibilities,
+    unit::{Unit, box_of},
+};
+
+/// A set of cells, one bit per cell at `row * 9 + col`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bitboard(u128);
+
+/// Cells of each unit, in the order of [`Unit::ALL`]
+static UNIT_BOARDS: [Bitboard; 27] = {
+    let mut boards = [Bitboard::EMPTY; 27];
+    let mut u = 0;
+    while u < 27 {
+        let unit = Unit::ALL[u];
+        let mut i = 0;
+        while i < 81 {
+            let (r, c) = (i / 9, i % 9);
+            let inside = match unit {
+                Unit::Row(row) => r == row,
+                Unit::Col(col) => c == col,
+                Unit::Box(b) => box_of((r, c)) == b,
+            };
+            if inside {
+                boards[u].0 |= 1 << i;
+            }
+            i += 1;
+        }
+        u += 1;
+    }
+    boards
+};
+
+impl Bitboard {
+    pub const EMPTY: Self = Self(0);
+    pub const ALL: Self = Self((1 << 81) - 1);
+
+    pub fn unit(unit: Unit) -> Self {
+        UNIT_BOARDS[unit.index()]
+    }
+
+    pub fn cell((r, c): (usize, usize)) -> Self {
+        Self(1 << (r * 9 + c))
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn contains(self, cell: (usize, usize)) -> bool {
+        !(self & Self::cell(cell)).is_empty()
+    }
+
+    /// Cells in the set, in row-major order
+    pub fn cells(self) -> impl Iterator<Item = (usize, usize)> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let i = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some((i / 9, i % 9))
+        })
+    }
+}
+
+impl ops::BitAnd for Bitboard {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl ops::BitOr for Bitboard {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl ops::BitAndAssign for Bitboard {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl ops::BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl ops::Not for Bitboard {
+    type Output = Self;
+
+    /// Complement within the 81 cells of the grid
+    fn not(self) -> Self {
+        Self(!self.0 & Self::ALL.0)
+    }
+}
+
+impl SudokuPossibilities {
+    /// Cells where `digit` is still a candidate
+    pub fn positions(&self, digit: Digit) -> Bitboard {
+        let mut board = Bitboard::EMPTY;
+        for (i, cell) in self.cells().iter().enumerate() {
+            if cell.contains(digit) {
+                board.0 |= 1 << i;
+            }
+        }
+        board
+    }
+
+    /// Cells where each digit is still a candidate, indexed by
+    /// [`Digit::index`], gathered in one pass over the grid
+    pub fn digit_boards(&self) -> [Bitboard; 9] {
+        let mut boards = [Bitboard::EMPTY; 9];
+        for (i, cell) in self.cells().iter().enumerate() {
+            for digit in *cell {
+                boards[digit.index() as usize].0 |= 1 << i;
+            }
+        }
+        boards
+    }
+
+    /// Cells whose candidates differ from those in `other`
+    pub fn changed_since(&self, other: &Self) -> Bitboard {
+        let mut board = Bitboard::EMPTY;
+        for (i, (a, b)) in self.cells().iter().zip(other.cells()).enumerate() {
+            if a != b {
+                board.0 |= 1 << i;
+            }
+        }
+        board
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_boards() {
+        for unit in Unit::ALL {
+            let board = Bitboard::unit(unit);
+            assert_eq!(board.count(), 9);
+            assert!(board.cells().eq(unit.cells()));
+        }
+        let corner = Bitboard::unit(Unit::Row(0)) & Bitboard::unit(Unit::Box(0));
+        assert_eq!(corner.count(), 3);
+        assert_eq!((!corner).count(), 78);
+        assert!(corner.contains((0, 2)));
+    }
+
+    #[test]
+    fn digit_boards_match_positions() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let (three, seven) = (Digit::unchecked(3), Digit::unchecked(7));
+        sp.grid[4][4].remove(three);
+        sp.grid[0][8] = crate::PossibleValues::from(seven);
+        let boards = sp.digit_boards();
+        for digit in crate::PossibleValues::ANY {
+            assert_eq!(boards[digit.index() as usize], sp.positions(digit));
+        }
+        assert_eq!(boards[three.index() as usize].count(), 79);
+        assert!(boards[seven.index() as usize].contains((0, 8)));
+    }
+}
@@ -1,43 +1,126 @@
-use std::ops;
+use std::{fmt, ops};
 
 use crate::Digit;
 
+/// Side length `N` of a grid, as a type to pick the storage of its
+/// candidate sets with [`SideLength`]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct PossibleValues(u16);
+pub struct Side<const N: usize>;
 
-impl PossibleValues {
-    pub const EMPTY: Self = Self(0);
-    pub const ANY: Self = Self(0b1_1111_1111);
+/// Storage of the candidates of grids with a given [`Side`]. Sides up to 16
+/// keep them in a `u16`, so 9×9 grids stay as small as they can be, and
+/// sides up to 32 in a `u32`.
+pub trait SideLength {
+    type Bits: Bits;
+    /// Bits of every digit from 1 to `N`
+    const ANY: Self::Bits;
+    const ODD: Self::Bits;
+    const EVEN: Self::Bits;
+}
+
+/// Unsigned integer with one bit per digit
+pub trait Bits:
+    Copy
+    + Eq
+    + fmt::Debug
+    + ops::BitAnd<Output = Self>
+    + ops::BitOr<Output = Self>
+    + ops::Not<Output = Self>
+    + ops::BitAndAssign
+    + ops::BitOrAssign
+{
+    const ZERO: Self;
+
+    /// Only bit `index` set
+    fn bit(index: u8) -> Self;
+    fn count_ones(self) -> u32;
+    fn trailing_zeros(self) -> u32;
+}
+
+macro_rules! bits {
+    ($($bits:ty),*) => {$(
+        impl Bits for $bits {
+            const ZERO: Self = 0;
+
+            fn bit(index: u8) -> Self {
+                1 << index
+            }
+
+            fn count_ones(self) -> u32 {
+                self.count_ones()
+            }
+
+            fn trailing_zeros(self) -> u32 {
+                self.trailing_zeros()
+            }
+        }
+    )*};
+}
 
-    pub fn initial_state(value: Option<Digit>) -> Self {
+bits!(u16, u32);
+
+macro_rules! side_lengths {
+    ($bits:ty: $($n:literal)*) => {$(
+        impl SideLength for Side<$n> {
+            type Bits = $bits;
+            const ANY: $bits = <$bits>::MAX >> (<$bits>::BITS - $n);
+            // Digit 1 is the lowest bit, so odd digits are the even bits
+            const ODD: $bits = <$bits>::MAX / 3 & Self::ANY;
+            const EVEN: $bits = !(<$bits>::MAX / 3) & Self::ANY;
+        }
+    )*};
+}
+
+side_lengths!(u16: 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16);
+side_lengths!(u32: 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32);
+
+/// Storage of the candidates of grids with side `N`
+type BitsOf<const N: usize> = <Side<N> as SideLength>::Bits;
+
+/// Set of candidate digits for a cell of a grid with `N` digits, 9 by
+/// default. Up to 32 digits fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PossibleValues<const N: usize = 9>(BitsOf<N>)
+where
+    Side<N>: SideLength;
+
+impl<const N: usize> PossibleValues<N>
+where
+    Side<N>: SideLength,
+{
+    pub const EMPTY: Self = Self(Bits::ZERO);
+    pub const ANY: Self = Self(<Side<N> as SideLength>::ANY);
+    pub const ODD: Self = Self(<Side<N> as SideLength>::ODD);
+    pub const EVEN: Self = Self(<Side<N> as SideLength>::EVEN);
+    pub fn initial_state(value: Option<Digit<N>>) -> Self {
         if let Some(n) = value {
             Self::from(n)
         } else {
-            Self(0b1_1111_1111)
+            Self::ANY
         }
     }
 
     pub fn is_broken(&self) -> bool {
-        self.0 == 0
+        self.0 == Bits::ZERO
     }
 
     pub fn count(&self) -> u8 {
         self.0.count_ones() as u8
     }
 
-    pub fn contains(&self, value: Digit) -> bool {
+    pub fn contains(&self, value: Digit<N>) -> bool {
         !(*self & Self::from(value)).is_broken()
     }
 
-    pub fn add(&mut self, value: Digit) {
+    pub fn add(&mut self, value: Digit<N>) {
         *self |= Self::from(value);
     }
 
-    pub fn remove(&mut self, value: Digit) {
-        self.0 &= !(1 << value.index());
+    pub fn remove(&mut self, value: Digit<N>) {
+        self.0 &= !BitsOf::<N>::bit(value.index());
     }
 
-    pub fn determined(&self) -> Option<Digit> {
+    pub fn determined(&self) -> Option<Digit<N>> {
         if self.count() == 1 {
             Some(
                 Digit::new(self.0.trailing_zeros() as u8 + 1)
@@ -48,53 +131,122 @@ impl PossibleValues {
         }
     }
 
-    pub fn options(&self) -> Vec<Digit> {
-        let mut result = Vec::new();
+    /// Candidates in ascending order, without allocating
+    pub fn iter(&self) -> Candidates<N> {
+        Candidates(self.0)
+    }
 
-        let mut tmp = self.0;
-        let mut num = Digit::MIN;
-        loop {
-            if tmp & 1 != 0 {
-                result.push(num);
-            }
-            tmp >>= 1;
-            if tmp == 0 {
-                break;
-            }
-            num = num.next().expect("Digit overflowed");
+    pub fn options(&self) -> Vec<Digit<N>> {
+        self.iter().collect()
+    }
+}
+
+impl PossibleValues {
+    /// Bit `digit.index()` is set for each candidate
+    pub(crate) fn bits(&self) -> u16 {
+        self.0
+    }
+
+    pub(crate) const fn from_bits(bits: u16) -> Self {
+        Self(bits & Self::ANY.0)
+    }
+}
+
+/// Candidates of a [`PossibleValues`], see [`PossibleValues::iter`]
+#[derive(Debug, Clone)]
+pub struct Candidates<const N: usize = 9>(BitsOf<N>)
+where
+    Side<N>: SideLength;
+
+impl<const N: usize> Iterator for Candidates<N>
+where
+    Side<N>: SideLength,
+{
+    type Item = Digit<N>;
+
+    fn next(&mut self) -> Option<Digit<N>> {
+        if self.0 == Bits::ZERO {
+            return None;
         }
-        result
+        let index = self.0.trailing_zeros() as u8;
+        self.0 &= !BitsOf::<N>::bit(index);
+        Digit::from_index(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
     }
 }
 
-impl ops::BitAnd for PossibleValues {
+impl<const N: usize> ExactSizeIterator for Candidates<N> where Side<N>: SideLength {}
+
+impl<const N: usize> IntoIterator for PossibleValues<N>
+where
+    Side<N>: SideLength,
+{
+    type Item = Digit<N>;
+    type IntoIter = Candidates<N>;
+
+    fn into_iter(self) -> Candidates<N> {
+        self.iter()
+    }
+}
+
+impl<const N: usize> ops::BitAnd for PossibleValues<N>
+where
+    Side<N>: SideLength,
+{
     type Output = Self;
 
     fn bitand(self, rhs: Self) -> Self::Output {
         Self(self.0 & rhs.0)
     }
 }
-impl ops::BitAndAssign for PossibleValues {
+impl<const N: usize> ops::BitAndAssign for PossibleValues<N>
+where
+    Side<N>: SideLength,
+{
     fn bitand_assign(&mut self, rhs: Self) {
         self.0 &= rhs.0;
     }
 }
-impl ops::BitOr for PossibleValues {
+impl<const N: usize> ops::BitOr for PossibleValues<N>
+where
+    Side<N>: SideLength,
+{
     type Output = Self;
 
     fn bitor(self, rhs: Self) -> Self::Output {
         Self(self.0 | rhs.0)
     }
 }
-impl ops::BitOrAssign for PossibleValues {
+impl<const N: usize> ops::BitOrAssign for PossibleValues<N>
+where
+    Side<N>: SideLength,
+{
     fn bitor_assign(&mut self, rhs: Self) {
         self.0 |= rhs.0;
     }
 }
 
-impl From<Digit> for PossibleValues {
-    fn from(value: Digit) -> Self {
-        Self(1 << value.index())
+impl<const N: usize> ops::Not for PossibleValues<N>
+where
+    Side<N>: SideLength,
+{
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self(!self.0 & Self::ANY.0)
+    }
+}
+
+impl<const N: usize> From<Digit<N>> for PossibleValues<N>
+where
+    Side<N>: SideLength,
+{
+    fn from(value: Digit<N>) -> Self {
+        Self(Bits::bit(value.index()))
     }
 }
 
@@ -103,7 +255,7 @@ mod tests {
     use super::*;
     #[test]
     fn test_possible_values_num() {
-        let mut pv = PossibleValues::initial_state(Some(Digit::unchecked(5)));
+        let mut pv: PossibleValues = PossibleValues::initial_state(Some(Digit::unchecked(5)));
         assert_eq!(pv.count(), 1);
         assert!(!pv.is_broken());
         pv.remove(Digit::unchecked(5));
@@ -112,7 +264,7 @@ mod tests {
 
     #[test]
     fn test_possible_values_any() {
-        let mut pv = PossibleValues::initial_state(None);
+        let mut pv: PossibleValues = PossibleValues::initial_state(None);
         assert_eq!(pv.count(), 9);
         assert!(!pv.is_broken());
         pv.remove(Digit::unchecked(1));
@@ -122,9 +274,9 @@ mod tests {
 
     #[test]
     fn test_possible_values_bitwise_ops() {
-        let pv1 = PossibleValues::initial_state(Some(Digit::unchecked(1)));
-        let pv2 = PossibleValues::initial_state(Some(Digit::unchecked(2)));
-        let pv3 = PossibleValues::initial_state(Some(Digit::unchecked(3)));
+        let pv1: PossibleValues = PossibleValues::initial_state(Some(Digit::unchecked(1)));
+        let pv2: PossibleValues = PossibleValues::initial_state(Some(Digit::unchecked(2)));
+        let pv3: PossibleValues = PossibleValues::initial_state(Some(Digit::unchecked(3)));
 
         let and_result = pv1 & pv2;
         let or_result = pv1 | pv3;
@@ -140,6 +292,12 @@ mod tests {
         assert!(!pv1.contains(Digit::unchecked(2)));
 
         assert_eq!(pv1.options(), vec![Digit::unchecked(1)]);
+        assert_eq!(PossibleValues::<9>::ANY.iter().len(), 9);
+        assert_eq!(
+            PossibleValues::<9>::ANY.iter().last(),
+            Some(Digit::unchecked(9))
+        );
+        assert_eq!(PossibleValues::<9>::EMPTY.iter().next(), None);
         assert_eq!(pv2.options(), vec![Digit::unchecked(2)]);
         assert_eq!(
             (pv1 | pv2).options(),
@@ -149,5 +307,22 @@ mod tests {
         assert_eq!(pv1.determined(), Some(Digit::unchecked(1)));
         assert_eq!(pv2.determined(), Some(Digit::unchecked(2)));
         assert_eq!((pv1 | pv2).determined(), None);
+
+        assert_eq!((!pv1).count(), 8);
+        assert!(!(!pv1).contains(Digit::unchecked(1)));
+        assert_eq!(!PossibleValues::<9>::ANY, PossibleValues::EMPTY);
+    }
+
+    #[test]
+    fn test_possible_values_sized() {
+        let any = PossibleValues::<16>::ANY;
+        assert_eq!(any.count(), 16);
+        assert_eq!(any.iter().last(), Digit::from_char('G'));
+        assert_eq!(PossibleValues::<16>::EVEN.count(), 8);
+        assert_eq!(PossibleValues::<25>::ANY.count(), 25);
+        assert_eq!(size_of::<PossibleValues>(), 2);
+        assert_eq!(size_of::<PossibleValues<25>>(), 4);
+        assert_eq!(Digit::<16>::unchecked(10).to_string(), "A");
+        assert_eq!(Digit::<9>::from_char('A'), None);
     }
 }
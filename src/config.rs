@@ -0,0 +1,238 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Instant,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+
+#[cfg(feature = "sat")]
+use crate::SatSolver;
+use crate::{
+    ConstraintSet, DlxSolver, PropagationSolver, SolveError, Solver, SudokuPossibilities,
+    strategy::{Strategy, StrategyRegistry},
+};
+
+/// Shared flag for stopping a solve from another thread.
+/// Clones refer to the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask every solve using this token to stop as soon as possible
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Algorithm used to find solutions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// Strategy-based inference with hypothetical search when it gets stuck
+    #[default]
+    Propagation,
+    /// Dancing Links exact cover search. Ignores the strategies.
+    Dlx,
+    /// Boolean satisfiability solver. Ignores the strategies.
+    #[cfg(feature = "sat")]
+    Sat,
+}
+
+/// Threads used to search guesses in parallel
+#[cfg(feature = "parallel")]
+#[derive(Debug, Clone, Default)]
+pub enum Parallelism {
+    /// The global rayon thread pool
+    #[default]
+    Global,
+    /// A pool built by the caller, such as with [`threads`](Self::threads)
+    Pool(Arc<ThreadPool>),
+}
+
+#[cfg(feature = "parallel")]
+impl Parallelism {
+    /// A new pool with this many threads. It is built once, and configs
+    /// cloned from the one holding it share it.
+    pub fn threads(threads: usize) -> Result<Self, ThreadPoolBuildError> {
+        let pool = ThreadPoolBuilder::new().num_threads(threads).build()?;
+        Ok(Self::Pool(Arc::new(pool)))
+    }
+
+    /// Run `op` in the selected thread pool
+    pub(crate) fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        match self {
+            Self::Global => op(),
+            Self::Pool(pool) => pool.install(op),
+        }
+    }
+}
+
+/// Order in which candidates of a cell are tried when guessing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ValueOrder {
+    /// From 1 to 9
+    #[default]
+    Ascending,
+    /// Digits that remove the fewest candidates from peers first
+    LeastConstraining,
+    /// Digits that have been placed the fewest times first
+    Frequency,
+    /// Shuffled, reproducibly for the same seed
+    Random { seed: u64 },
+}
+
+/// Options controlling how puzzles are solved
+#[derive(Debug, Clone)]
+pub struct SolverConfig {
+    pub backend: Backend,
+    /// Strategies to try, in order. After each successful one, inference
+    /// restarts from the first, so cheap strategies should come first.
+    pub strategies: StrategyRegistry,
+    /// Rules that solutions must follow. Only the propagation backend
+    /// honors constraints beyond those of classic sudoku, and the others
+    /// fail with [`SolveError::Unsupported`] if there are any.
+    pub constraints: ConstraintSet,
+    /// Use techniques that are only sound when the puzzle has exactly one solution,
+    /// such as unique rectangles. On puzzles with several solutions this may remove
+    /// all of them. Ignored unless the puzzle is classic sudoku, since other rules
+    /// can tell apart the grids those techniques treat as interchangeable.
+    pub assume_unique_solution: bool,
+    /// Order in which candidates are tried when inference gets stuck.
+    /// Ignored in deterministic mode.
+    pub value_order: ValueOrder,
+    /// Always return the lexicographically smallest solution, comparing
+    /// cells row by row. Guessing then goes through the cells in order
+    /// instead of picking the most constrained one, which can be slower.
+    /// Only the propagation backend honors it.
+    pub deterministic: bool,
+    /// Give up solving once this time has passed. Only the propagation
+    /// backend checks it, before each round of inference and each state it
+    /// searches, so a single slow strategy can still overrun it.
+    pub deadline: Option<Instant>,
+    /// Stop solving once this token is cancelled. Checked as often as
+    /// [`deadline`](Self::deadline).
+    pub cancellation: Option<CancellationToken>,
+    /// Give up after inferring this many guessed states in one solve,
+    /// counted across every branch. Only the propagation backend checks it.
+    pub max_nodes: Option<usize>,
+    /// Give up once a state needs more nested guesses than this.
+    /// A [`Search`](crate::Search) stopped by it can continue with a higher
+    /// limit, keeping the states it has already inferred.
+    /// Only the propagation backend checks it.
+    pub max_depth: Option<usize>,
+    /// Number of guess levels whose branches are searched in parallel.
+    /// Deeper guesses are searched one at a time within their branch, and
+    /// with 0 the whole search runs on the calling thread.
+    pub parallel_depth: usize,
+    #[cfg(feature = "parallel")]
+    pub parallelism: Parallelism,
+}
+
+impl SolverConfig {
+    /// Only singles and locked candidates, enough for easy puzzles
+    pub fn basic() -> Self {
+        Self {
+            strategies: StrategyRegistry::basic(),
+            ..Default::default()
+        }
+    }
+
+    /// Every built-in technique, including Jellyfish
+    pub fn extended() -> Self {
+        Self {
+            strategies: StrategyRegistry::extended(),
+            ..Default::default()
+        }
+    }
+
+    /// Engine for the selected backend
+    pub fn solver(&self) -> Box<dyn Solver> {
+        match self.backend {
+            Backend::Propagation => Box::new(PropagationSolver {
+                config: self.clone(),
+            }),
+            Backend::Dlx => Box::new(DlxSolver {
+                constraints: self.constraints.clone(),
+            }),
+            #[cfg(feature = "sat")]
+            Backend::Sat => Box::new(SatSolver {
+                constraints: self.constraints.clone(),
+            }),
+        }
+    }
+
+    /// Strategies that will actually run, in order
+    pub fn enabled_strategies(&self) -> impl Iterator<Item = &dyn Strategy> {
+        let uniqueness = self.assume_unique_solution && self.constraints.is_classic();
+        self.strategies
+            .iter()
+            .filter(move |strategy| uniqueness || !strategy.assumes_unique_solution())
+    }
+
+    /// Error to stop with if the solve has been cancelled or has run out of
+    /// time, given the state it was working on. Checked between rounds of
+    /// inference as well as between the states of a search.
+    pub(crate) fn halt(&self, state: &SudokuPossibilities) -> Option<SolveError> {
+        if self
+            .cancellation
+            .as_ref()
+            .is_some_and(|token| token.is_cancelled())
+        {
+            Some(SolveError::Cancelled(Box::new(*state)))
+        } else if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            Some(SolveError::Timeout(Box::new(*state)))
+        } else {
+            None
+        }
+    }
+
+    /// Error to stop with if the search has been cancelled or has run out of
+    /// time or nodes, given the state it was about to search
+    pub(crate) fn interruption(
+        &self,
+        state: &SudokuPossibilities,
+        nodes: usize,
+    ) -> Option<SolveError> {
+        if let Some(error) = self.halt(state) {
+            Some(error)
+        } else if self.max_nodes.is_some_and(|max| nodes >= max) {
+            Some(SolveError::BudgetExceeded(Box::new(*state)))
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self {
+            backend: Backend::default(),
+            strategies: StrategyRegistry::standard(),
+            constraints: ConstraintSet::classic(),
+            assume_unique_solution: false,
+            value_order: ValueOrder::default(),
+            deterministic: true,
+            deadline: None,
+            cancellation: None,
+            max_nodes: None,
+            max_depth: None,
+            parallel_depth: 1,
+            #[cfg(feature = "parallel")]
+            parallelism: Parallelism::default(),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{Digit, Grid, SudokuPossibilities};
+
+use super::Constraint;
+
+/// Cells a king's move apart on a diagonal never hold the same digit.
+/// Orthogonal neighbours already share a row or column.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AntiKing;
+
+impl AntiKing {
+    /// Cells diagonally adjacent to the given one
+    pub fn neighbours((r, c): (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        [(-1, -1), (-1, 1), (1, -1), (1, 1)]
+            .into_iter()
+            .filter_map(move |(dr, dc)| {
+                Some((r.checked_add_signed(dr)?, c.checked_add_signed(dc)?))
+            })
+            .filter(|&(r, c)| r < 9 && c < 9)
+    }
+}
+
+impl Constraint for AntiKing {
+    fn name(&self) -> &str {
+        "Anti-King"
+    }
+
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let mut progress = false;
+        for i in 0..81 {
+            let (r, c) = (i / 9, i % 9);
+            let Some(digit) = sp.grid[r][c].determined() else {
+                continue;
+            };
+            for (nr, nc) in Self::neighbours((r, c)) {
+                if sp.grid[nr][nc].contains(digit) {
+                    sp.grid[nr][nc].remove(digit);
+                    progress = true;
+                }
+            }
+        }
+        progress
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        (0..81).all(|i| {
+            let (r, c) = (i / 9, i % 9);
+            Self::neighbours((r, c)).all(|(nr, nc)| grid.grid[nr][nc] != grid.grid[r][c])
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Puzzle;
+
+    #[test]
+    fn solve_anti_king() {
+        let data =
+            std::fs::read_to_string("puzzles/variants/anti-king.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        assert!(AntiKing.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.is_solution(solution.grid()));
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+
+    #[test]
+    fn corner_neighbours() {
+        assert!(AntiKing::neighbours((0, 0)).eq([(1, 1)]));
+        assert_eq!(AntiKing::neighbours((4, 8)).count(), 2);
+        assert_eq!(AntiKing::neighbours((4, 4)).count(), 4);
+    }
+}
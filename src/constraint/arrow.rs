@@ -0,0 +1,169 @@
+use crate::{Digit, Grid, PossibleValues, SudokuPossibilities};
+
+use super::Constraint;
+
+/// Set of sums from 0 to 127, bit `n` for the sum `n`
+type Sums = u128;
+
+/// Arrow: the digits along the arrow add up to the number in its circle.
+/// The circle may be a pill of up to 3 cells, read as a number from its
+/// first cell. Digits may repeat along the arrow where sudoku allows it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arrow {
+    pill: Vec<(usize, usize)>,
+    cells: Vec<(usize, usize)>,
+}
+
+impl Arrow {
+    /// An arrow from a circle or pill to its cells. Returns `None` if the
+    /// pill is empty or longer than 3 cells, if the arrow is empty or longer
+    /// than 14 cells, or if a cell is listed twice.
+    pub fn new(pill: Vec<(usize, usize)>, cells: Vec<(usize, usize)>) -> Option<Self> {
+        let all: Vec<_> = pill.iter().chain(&cells).collect();
+        let distinct = all
+            .iter()
+            .enumerate()
+            .all(|(i, cell)| !all[..i].contains(cell));
+        if !(1..=3).contains(&pill.len()) || !(1..=14).contains(&cells.len()) || !distinct {
+            return None;
+        }
+        Some(Self { pill, cells })
+    }
+
+    pub fn pill(&self) -> &[(usize, usize)] {
+        &self.pill
+    }
+
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+}
+
+fn value(digit: Digit) -> u32 {
+    u32::from(digit.index()) + 1
+}
+
+/// Sums of a number from `sums` and a candidate of `cell`
+fn add(sums: Sums, cell: PossibleValues) -> Sums {
+    cell.iter().fold(0, |all, digit| all | sums << value(digit))
+}
+
+/// Sums of a number from `a` and a number from `b`
+fn add_sums(a: Sums, b: Sums) -> Sums {
+    (0..Sums::BITS)
+        .filter(|n| a & 1 << n != 0)
+        .fold(0, |all, n| all | b << n)
+}
+
+impl Constraint for Arrow {
+    fn name(&self) -> &str {
+        "Arrow"
+    }
+
+    /// Only pill values that the arrow can add up to are kept, and only
+    /// arrow digits with which the rest of the arrow can reach one of them.
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let arrow: Vec<_> = self.cells.iter().map(|&(r, c)| sp.grid[r][c]).collect();
+        let mut prefix = vec![1];
+        for &cell in &arrow {
+            prefix.push(add(prefix[prefix.len() - 1], cell));
+        }
+        let mut suffix = vec![1; arrow.len() + 1];
+        for (i, &cell) in arrow.iter().enumerate().rev() {
+            suffix[i] = add(suffix[i + 1], cell);
+        }
+        let totals = prefix[arrow.len()];
+
+        // Every way of filling the pill, with the number it reads
+        let mut fillings = vec![(0, Vec::new())];
+        for &(r, c) in &self.pill {
+            fillings = fillings
+                .into_iter()
+                .flat_map(|(number, digits): (u32, Vec<Digit>)| {
+                    sp.grid[r][c].iter().map(move |digit| {
+                        let mut digits = digits.clone();
+                        digits.push(digit);
+                        (number * 10 + value(digit), digits)
+                    })
+                })
+                .collect();
+        }
+        let mut values: Sums = 0;
+        let mut pill = vec![PossibleValues::EMPTY; self.pill.len()];
+        for (number, digits) in fillings {
+            if number < Sums::BITS && totals & 1 << number != 0 {
+                values |= 1 << number;
+                for (supported, digit) in pill.iter_mut().zip(digits) {
+                    supported.add(digit);
+                }
+            }
+        }
+
+        let mut progress = false;
+        for (&(r, c), supported) in self.pill.iter().zip(pill) {
+            if sp.grid[r][c] & supported != sp.grid[r][c] {
+                sp.grid[r][c] &= supported;
+                progress = true;
+            }
+        }
+        for (i, &(r, c)) in self.cells.iter().enumerate() {
+            let rest = add_sums(prefix[i], suffix[i + 1]);
+            for digit in sp.grid[r][c] {
+                if (rest << value(digit)) & values == 0 {
+                    sp.grid[r][c].remove(digit);
+                    progress = true;
+                }
+            }
+        }
+        progress
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        let number = self
+            .pill
+            .iter()
+            .fold(0, |number, &(r, c)| number * 10 + value(grid.grid[r][c]));
+        let total: u32 = self
+            .cells
+            .iter()
+            .map(|&(r, c)| value(grid.grid[r][c]))
+            .sum();
+        number == total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Puzzle;
+
+    #[test]
+    fn prune_sums() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        // Three cells add up to at least 3
+        let arrow = Arrow::new(vec![(0, 0)], vec![(1, 0), (2, 0), (2, 1)]).unwrap();
+        sp.grid[2][1] = PossibleValues::from(Digit::unchecked(5));
+        assert!(arrow.propagate(&mut sp));
+        assert_eq!(sp.grid[0][0].iter().next(), Some(Digit::unchecked(7)));
+        assert_eq!(sp.grid[1][0].iter().last(), Some(Digit::unchecked(3)));
+        assert!(!arrow.propagate(&mut sp));
+
+        // A pill of two cells reads at least 11, more than two cells can reach
+        let arrow = Arrow::new(vec![(4, 4), (4, 5)], vec![(5, 5), (6, 5)]).unwrap();
+        assert!(arrow.propagate(&mut sp));
+        assert_eq!(sp.grid[4][4], PossibleValues::from(Digit::unchecked(1)));
+        assert_eq!(sp.grid[4][5].iter().last(), Some(Digit::unchecked(8)));
+        assert_eq!(sp.grid[5][5].iter().next(), Some(Digit::unchecked(2)));
+
+        assert_eq!(Arrow::new(vec![(0, 0)], vec![(0, 0)]), None);
+    }
+
+    #[test]
+    fn solve_arrows() {
+        let data = std::fs::read_to_string("puzzles/variants/arrow.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+}
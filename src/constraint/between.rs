@@ -0,0 +1,151 @@
+use crate::{Digit, Grid, PossibleValues, SudokuPossibilities};
+
+use super::Constraint;
+
+/// Between line: the digits on the line are strictly between the digits in
+/// the circles at its ends, in either order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Between {
+    cells: Vec<(usize, usize)>,
+}
+
+impl Between {
+    /// A line through `cells`, with the circles first and last. Returns
+    /// `None` if it has fewer than two cells or a cell is listed twice.
+    pub fn new(cells: Vec<(usize, usize)>) -> Option<Self> {
+        let distinct = cells
+            .iter()
+            .enumerate()
+            .all(|(i, cell)| !cells[..i].contains(cell));
+        (cells.len() >= 2 && distinct).then_some(Self { cells })
+    }
+
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+
+    fn ends(&self) -> [(usize, usize); 2] {
+        [self.cells[0], self.cells[self.cells.len() - 1]]
+    }
+
+    fn inner(&self) -> &[(usize, usize)] {
+        &self.cells[1..self.cells.len() - 1]
+    }
+}
+
+fn value(digit: Digit) -> u8 {
+    digit.index() + 1
+}
+
+/// Candidates strictly between `low` and `high`
+fn within(cell: PossibleValues, low: u8, high: u8) -> PossibleValues {
+    cell.iter()
+        .filter(|&digit| low < value(digit) && value(digit) < high)
+        .fold(PossibleValues::EMPTY, |kept, digit| kept | digit.into())
+}
+
+/// Smallest and largest candidate of a cell, if it has any
+fn bounds(cell: PossibleValues) -> Option<(u8, u8)> {
+    Some((value(cell.iter().next()?), value(cell.iter().last()?)))
+}
+
+impl Constraint for Between {
+    fn name(&self) -> &str {
+        "Between Line"
+    }
+
+    /// With either end as the low one, the low end must be below every cell
+    /// on the line and the high end above, and the line must fit between
+    /// them. The candidates of both cases are kept.
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let inner: Option<Vec<_>> = self
+            .inner()
+            .iter()
+            .map(|&(r, c)| bounds(sp.grid[r][c]))
+            .collect();
+        let Some(inner) = inner else {
+            return false;
+        };
+        // The smallest digit on the line is at most this, the largest at least
+        let lowest = inner.iter().map(|&(_, max)| max).min().unwrap_or(10);
+        let highest = inner.iter().map(|&(min, _)| min).max().unwrap_or(0);
+
+        let [(ar, ac), (br, bc)] = self.ends();
+        let (a, b) = (sp.grid[ar][ac], sp.grid[br][bc]);
+        let mut kept = vec![PossibleValues::EMPTY; self.cells.len()];
+        for (low, high, low_slot, high_slot) in
+            [(a, b, 0, kept.len() - 1), (b, a, kept.len() - 1, 0)]
+        {
+            let (Some(low_bounds), Some(high_bounds)) = (bounds(low), bounds(high)) else {
+                continue;
+            };
+            let low = within(low, 0, lowest.min(high_bounds.1));
+            let high = within(high, highest.max(low_bounds.0), 10);
+            let (Some((low_min, _)), Some((_, high_max))) = (bounds(low), bounds(high)) else {
+                continue;
+            };
+            kept[low_slot] |= low;
+            kept[high_slot] |= high;
+            for (slot, &(r, c)) in kept[1..].iter_mut().zip(self.inner()) {
+                *slot |= within(sp.grid[r][c], low_min, high_max);
+            }
+        }
+
+        let mut progress = false;
+        for (&(r, c), kept) in self.cells.iter().zip(kept) {
+            if sp.grid[r][c] & kept != sp.grid[r][c] {
+                sp.grid[r][c] &= kept;
+                progress = true;
+            }
+        }
+        progress
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        let [a, b] = self.ends().map(|(r, c)| value(grid.grid[r][c]));
+        let (low, high) = (a.min(b), a.max(b));
+        low < high
+            && self.inner().iter().all(|&(r, c)| {
+                let digit = value(grid.grid[r][c]);
+                low < digit && digit < high
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Puzzle;
+
+    #[test]
+    fn ends_bound_the_line() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let line = Between::new(vec![(0, 0), (0, 1), (0, 2), (1, 2)]).unwrap();
+        sp.grid[0][0] = PossibleValues::from(Digit::unchecked(3));
+        sp.grid[0][1] &= PossibleValues::from(Digit::unchecked(5)) | Digit::unchecked(6).into();
+        assert!(line.propagate(&mut sp));
+        // The 3 must be the low end, since 5 or 6 is above it
+        assert_eq!(sp.grid[1][2].options(), [6, 7, 8, 9].map(Digit::unchecked));
+        assert_eq!(
+            sp.grid[0][2].options(),
+            [4, 5, 6, 7, 8].map(Digit::unchecked)
+        );
+        assert!(!line.propagate(&mut sp));
+
+        // Nothing fits between 4 and 5
+        let short = Between::new(vec![(4, 4), (4, 5), (4, 6)]).unwrap();
+        sp.grid[4][4] = PossibleValues::from(Digit::unchecked(4));
+        sp.grid[4][6] = PossibleValues::from(Digit::unchecked(5));
+        short.propagate(&mut sp);
+        assert!(sp.grid[4][5].is_broken());
+    }
+
+    #[test]
+    fn solve_between_lines() {
+        let data = std::fs::read_to_string("puzzles/variants/between.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+}
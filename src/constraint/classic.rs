@@ -0,0 +1,96 @@
+use crate::{Digit, Grid, SudokuPossibilities, unit::Unit};
+
+use super::{Constraint, all_different, all_distinct};
+
+/// Propagate every unit of one kind
+fn propagate_units(sp: &mut SudokuPossibilities, units: &[Unit]) -> bool {
+    let mut progress = false;
+    for unit in units {
+        progress |= all_different(sp, &unit.cells());
+    }
+    progress
+}
+
+fn units_satisfied(grid: &Grid<Digit>, units: &[Unit]) -> bool {
+    units.iter().all(|unit| all_distinct(grid, &unit.cells()))
+}
+
+/// Each row holds every digit once
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rows;
+
+impl Constraint for Rows {
+    fn name(&self) -> &str {
+        "Rows"
+    }
+
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        propagate_units(sp, &Unit::ALL[..9])
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        units_satisfied(grid, &Unit::ALL[..9])
+    }
+}
+
+/// Each column holds every digit once
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Columns;
+
+impl Constraint for Columns {
+    fn name(&self) -> &str {
+        "Columns"
+    }
+
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        propagate_units(sp, &Unit::ALL[9..18])
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        units_satisfied(grid, &Unit::ALL[9..18])
+    }
+}
+
+/// Each 3×3 box holds every digit once
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Boxes;
+
+impl Constraint for Boxes {
+    fn name(&self) -> &str {
+        "Boxes"
+    }
+
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        propagate_units(sp, &Unit::ALL[18..])
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        units_satisfied(grid, &Unit::ALL[18..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConstraintSet, Sudoku};
+
+    #[test]
+    fn classic_rules_match_solutions() {
+        let data = std::fs::read_to_string("puzzles/wiki.txt").expect("Missing puzzle");
+        let sudoku = Sudoku::parse(&data).unwrap();
+        let solution = sudoku.solve().unwrap();
+        assert!(ConstraintSet::classic().is_satisfied(solution.grid()));
+
+        let mut grid = Grid::from(solution);
+        grid.grid[0].swap(0, 1);
+        assert!(Rows.is_satisfied(&grid));
+        assert!(!Columns.is_satisfied(&grid));
+
+        // The rules alone get at least as far as propagation
+        let mut sp = SudokuPossibilities::from(sudoku);
+        while Rows.propagate(&mut sp) | Columns.propagate(&mut sp) | Boxes.propagate(&mut sp) {}
+        let mut propagated = sp;
+        propagated.propagate().unwrap();
+        assert_eq!(sp, propagated);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{Digit, Grid, SudokuPossibilities};
+
+use super::{
+    Constraint,
+    pairs::{Pair, prune},
+};
+
+/// Clone: two regions of the same shape hold the same digits, cell by cell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clones {
+    pairs: Vec<Pair>,
+}
+
+impl Clones {
+    /// Regions whose cells correspond in the order given. Returns `None` if
+    /// they have different sizes, or if a cell is listed twice in either.
+    pub fn new(a: Vec<(usize, usize)>, b: Vec<(usize, usize)>) -> Option<Self> {
+        let distinct = |cells: &[(usize, usize)]| {
+            cells
+                .iter()
+                .enumerate()
+                .all(|(i, cell)| !cells[..i].contains(cell))
+        };
+        if a.len() != b.len() || !distinct(&a) || !distinct(&b) {
+            return None;
+        }
+        Some(Self {
+            pairs: a.into_iter().zip(b).collect(),
+        })
+    }
+
+    /// Corresponding cells of the two regions
+    pub fn pairs(&self) -> &[Pair] {
+        &self.pairs
+    }
+}
+
+impl Constraint for Clones {
+    fn name(&self) -> &str {
+        "Clone"
+    }
+
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let mut progress = false;
+        for &pair in &self.pairs {
+            progress |= prune(sp, pair, |a, b| a == b);
+        }
+        progress
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        self.pairs
+            .iter()
+            .all(|&((ar, ac), (br, bc))| grid.grid[ar][ac] == grid.grid[br][bc])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PossibleValues, Puzzle};
+
+    #[test]
+    fn copy_candidates() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let clones = Clones::new(vec![(0, 0), (0, 1)], vec![(4, 4), (4, 5)]).unwrap();
+        sp.grid[0][0] = PossibleValues::from(Digit::unchecked(3)) | Digit::unchecked(4).into();
+        sp.grid[4][4].remove(Digit::unchecked(4));
+        sp.grid[0][1].remove(Digit::unchecked(9));
+        assert!(clones.propagate(&mut sp));
+        assert_eq!(sp.grid[4][4], PossibleValues::from(Digit::unchecked(3)));
+        assert_eq!(sp.grid[0][0], sp.grid[4][4]);
+        assert!(!sp.grid[4][5].contains(Digit::unchecked(9)));
+        assert!(!clones.propagate(&mut sp));
+
+        assert!(Clones::new(vec![(0, 0)], vec![(1, 1), (1, 2)]).is_none());
+    }
+
+    #[test]
+    fn solve_clones() {
+        let data = std::fs::read_to_string("puzzles/variants/clone.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{Digit, Grid, SudokuPossibilities};
+
+use super::{Constraint, all_different, all_distinct};
+
+/// Both main diagonals hold every digit once, as in Sudoku X
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Diagonals;
+
+impl Diagonals {
+    /// The diagonal from the top left corner, then the one from the top right
+    pub const CELLS: [[(usize, usize); 9]; 2] = {
+        let mut cells = [[(0, 0); 9]; 2];
+        let mut i = 0;
+        while i < 9 {
+            cells[0][i] = (i, i);
+            cells[1][i] = (i, 8 - i);
+            i += 1;
+        }
+        cells
+    };
+}
+
+impl Constraint for Diagonals {
+    fn name(&self) -> &str {
+        "Diagonals"
+    }
+
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let mut progress = false;
+        for diagonal in &Self::CELLS {
+            progress |= all_different(sp, diagonal);
+        }
+        progress
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        Self::CELLS
+            .iter()
+            .all(|diagonal| all_distinct(grid, diagonal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Puzzle;
+
+    #[test]
+    fn solve_sudoku_x() {
+        let data = std::fs::read_to_string("puzzles/variants/x.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        assert_eq!(
+            format!("{:?}", puzzle.constraints),
+            r#"["Rows", "Columns", "Boxes", "Diagonals"]"#
+        );
+        let solution = puzzle.solve().unwrap();
+        assert!(Diagonals.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.is_solution(solution.grid()));
+
+        // The givens alone have other solutions that break the diagonals
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{
+    Digit, Grid, SudokuPossibilities,
+    unit::{Unit, Units},
+};
+
+use super::{Constraint, all_distinct};
+
+/// Jigsaw: nine irregular regions of nine cells replace the boxes, and each
+/// holds every digit once.
+///
+/// Set it with [`ConstraintSet::with_regions`](super::ConstraintSet::with_regions),
+/// so that the boxes no longer apply and the regions are propagated along
+/// with the rows and columns. The built-in strategies work on the regions
+/// in place of the boxes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Jigsaw {
+    regions: [[usize; 9]; 9],
+    units: Box<Units>,
+}
+
+impl Jigsaw {
+    /// Regions given by the index from 0 to 8 of the region of each cell.
+    /// Returns `None` unless every region has nine cells.
+    pub fn new(regions: [[usize; 9]; 9]) -> Option<Self> {
+        let mut sizes = [0; 9];
+        for &region in regions.iter().flatten() {
+            *sizes.get_mut(region)? += 1;
+        }
+        if sizes != [9; 9] {
+            return None;
+        }
+        Some(Self {
+            regions,
+            units: Box::new(Units::new(&regions)),
+        })
+    }
+
+    /// Parse a region map of nine rows of nine characters, with the same
+    /// character for each cell of a region, such as `AAABBBCCC`. Returns
+    /// `None` unless it has nine regions of nine cells.
+    pub fn parse<'a>(rows: impl IntoIterator<Item = &'a str>) -> Option<Self> {
+        let mut labels = Vec::new();
+        let mut regions = [[0; 9]; 9];
+        let mut rows = rows.into_iter();
+        for row in &mut regions {
+            let mut chars = rows.next()?.chars();
+            for cell in row.iter_mut() {
+                let label = chars.next()?;
+                *cell = match labels.iter().position(|&l| l == label) {
+                    Some(region) => region,
+                    None => {
+                        labels.push(label);
+                        labels.len() - 1
+                    }
+                };
+            }
+            if chars.next().is_some() {
+                return None;
+            }
+        }
+        if rows.next().is_some() {
+            return None;
+        }
+        Self::new(regions)
+    }
+
+    /// Index of the region containing the given cell
+    pub fn region_of(&self, (row, col): (usize, usize)) -> usize {
+        self.regions[row][col]
+    }
+
+    /// Cells of a region, by its index from 0 to 8
+    pub fn region(&self, region: usize) -> [(usize, usize); 9] {
+        self.units.cells(Unit::Box(region))
+    }
+
+    /// Rows, columns and regions
+    pub fn units(&self) -> &Units {
+        &self.units
+    }
+}
+
+impl Constraint for Jigsaw {
+    fn name(&self) -> &str {
+        "Jigsaw"
+    }
+
+    /// Nothing to do, since propagation handles the regions like boxes
+    fn propagate(&self, _sp: &mut SudokuPossibilities) -> bool {
+        false
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        (0..9).all(|region| all_distinct(grid, &self.region(region)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ConstraintSet, Puzzle, SolverConfig, SudokuSolution,
+        strategy::{StrategyRegistry, Technique},
+        unit::box_of,
+    };
+
+    #[test]
+    fn parse_region_map() {
+        let boxes = Jigsaw::parse([
+            "AAABBBCCC",
+            "AAABBBCCC",
+            "AAABBBCCC",
+            "DDDEEEFFF",
+            "DDDEEEFFF",
+            "DDDEEEFFF",
+            "GGGHHHIII",
+            "GGGHHHIII",
+            "GGGHHHIII",
+        ])
+        .unwrap();
+        for cell in boxes.region(4) {
+            assert_eq!(box_of(cell), 4);
+            assert_eq!(boxes.region_of(cell), 4);
+        }
+        let empty = ConstraintSet::classic();
+        assert_eq!(boxes.units(), empty.units());
+
+        // A single region, and a missing row
+        assert!(Jigsaw::parse(["AAAAAAAAA"; 9].iter().copied()).is_none());
+        assert!(Jigsaw::parse(["AAABBBCCC"; 8].iter().copied()).is_none());
+    }
+
+    #[test]
+    fn solve_jigsaw() {
+        let data = std::fs::read_to_string("puzzles/variants/jigsaw.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        assert_eq!(puzzle.sudoku.validate_with(&puzzle.constraints), Ok(()));
+        let solution = puzzle.solve().unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+        // The regions replace the boxes, which the solution breaks
+        assert!(SudokuSolution::new(*solution.grid()).is_none());
+    }
+
+    #[test]
+    fn strategies_use_regions() {
+        let data = std::fs::read_to_string("puzzles/variants/jigsaw.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        let stuck = |strategies| {
+            let config = SolverConfig {
+                strategies,
+                ..puzzle.config()
+            };
+            let mut sp = SudokuPossibilities::from(puzzle.sudoku);
+            sp.infer(&config).unwrap();
+            sp
+        };
+        let (plain, inferred) = (
+            stuck([Technique::HiddenSingles].into_iter().collect()),
+            stuck(StrategyRegistry::standard()),
+        );
+        let count = |sp: SudokuPossibilities| {
+            sp.cells()
+                .iter()
+                .map(|cell| u32::from(cell.count()))
+                .sum::<u32>()
+        };
+        assert!(count(inferred) < count(plain));
+        for (r, c) in (0..81).map(|i| (i / 9, i % 9)) {
+            assert!(inferred.grid[r][c].contains(solution.grid().grid[r][c]));
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{Digit, Grid, PossibleValues, SudokuPossibilities};
+
+use super::{
+    Constraint, all_distinct,
+    sums::{combinations, supported},
+};
+
+/// Killer cage: the digits of the cells add up to `sum` and don't repeat
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cage {
+    sum: u32,
+    cells: Vec<(usize, usize)>,
+    /// Sets of digits that add up to the sum with one digit per cell
+    combinations: Vec<PossibleValues>,
+}
+
+impl Cage {
+    /// A cage of at most 9 cells. Returns `None` if there are more, or if a
+    /// cell is listed twice.
+    pub fn new(sum: u32, cells: Vec<(usize, usize)>) -> Option<Self> {
+        let distinct = cells
+            .iter()
+            .enumerate()
+            .all(|(i, cell)| !cells[..i].contains(cell));
+        if cells.len() > 9 || !distinct {
+            return None;
+        }
+        let combinations = combinations(cells.len(), sum, PossibleValues::ANY);
+        Some(Self {
+            sum,
+            cells,
+            combinations,
+        })
+    }
+
+    pub fn sum(&self) -> u32 {
+        self.sum
+    }
+
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+}
+
+impl Constraint for Cage {
+    fn name(&self) -> &str {
+        "Killer Cage"
+    }
+
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let mut progress = false;
+        let candidates: Vec<_> = self.cells.iter().map(|&(r, c)| sp.grid[r][c]).collect();
+        let supported = supported(&candidates, &self.combinations);
+        for (&(r, c), supported) in self.cells.iter().zip(supported) {
+            let cell = &mut sp.grid[r][c];
+            if *cell & supported != *cell {
+                *cell &= supported;
+                progress = true;
+            }
+        }
+        progress
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        let total: u32 = self
+            .cells
+            .iter()
+            .map(|&(r, c)| u32::from(grid.grid[r][c].index()) + 1)
+            .sum();
+        total == self.sum && all_distinct(grid, &self.cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Puzzle, SolverConfig};
+
+    #[test]
+    fn prune_combinations() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        // 3 in two cells can only be 1 + 2
+        let cage = Cage::new(3, vec![(0, 0), (0, 1)]).unwrap();
+        assert!(cage.propagate(&mut sp));
+        let low =
+            PossibleValues::from(Digit::unchecked(1)) | PossibleValues::from(Digit::unchecked(2));
+        assert_eq!(sp.grid[0][0], low);
+        assert_eq!(sp.grid[0][1], low);
+        assert!(!cage.propagate(&mut sp));
+
+        // 24 in three cells is 7 + 8 + 9, so a cell without them is broken
+        let cage = Cage::new(24, vec![(4, 4), (4, 5), (5, 5)]).unwrap();
+        sp.grid[4][4] = PossibleValues::from(Digit::unchecked(9));
+        sp.grid[5][5] = PossibleValues::from(Digit::unchecked(9));
+        assert!(cage.propagate(&mut sp));
+        assert!(sp.grid[4][5].is_broken());
+
+        assert_eq!(Cage::new(3, vec![(0, 0), (0, 0)]), None);
+    }
+
+    #[test]
+    fn solve_killer() {
+        let data = std::fs::read_to_string("puzzles/variants/killer.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        assert!(puzzle.sudoku.iter().all(Option::is_none));
+        let config = SolverConfig {
+            deterministic: false,
+            ..puzzle.config()
+        };
+        let solution = puzzle.sudoku.solve_with(&config).unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+        assert_eq!(puzzle.sudoku.count_solutions_with(&config, 2), Ok(1));
+    }
+
+    #[test]
+    fn cage_breaks_deadly_pattern() {
+        // Without the cage, swapping digits in a rectangle gives another
+        // solution, so uniqueness techniques would remove the real one
+        let puzzle = Puzzle::parse(
+            ".34....1.\n.7..95..8\n........7\n8..7..42.\n.2..53...\n\
+             ...9..8.6\n......2..\n.8..1..35\n3......7.\ncage 5 r3c6 r3c4",
+        )
+        .unwrap();
+        assert_eq!(puzzle.sudoku.count_solutions(2), 2);
+        assert_eq!(
+            puzzle.sudoku.count_solutions_with(&puzzle.config(), 2),
+            Ok(1)
+        );
+        let config = SolverConfig {
+            assume_unique_solution: true,
+            ..puzzle.config()
+        };
+        assert!(
+            config
+                .enabled_strategies()
+                .all(|strategy| !strategy.assumes_unique_solution())
+        );
+        assert_eq!(puzzle.sudoku.solve_with(&config), puzzle.solve());
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{Digit, Grid, SudokuPossibilities};
+
+use super::{
+    Constraint,
+    pairs::{Mark, Marks, Pair},
+};
+
+/// Kropki dot between two cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dot {
+    /// The digits are consecutive
+    White,
+    /// One digit is double the other
+    Black,
+}
+
+impl Mark for Dot {
+    const ALL: &'static [Self] = &[Self::White, Self::Black];
+
+    fn holds(self, a: Digit, b: Digit) -> bool {
+        let (a, b) = (a.index() + 1, b.index() + 1);
+        match self {
+            Self::White => a.abs_diff(b) == 1,
+            Self::Black => a == 2 * b || b == 2 * a,
+        }
+    }
+}
+
+/// Kropki dots between pairs of cells
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Kropki(Marks<Dot>);
+
+impl Kropki {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Put a dot between two cells
+    pub fn add(&mut self, dot: Dot, cells: Pair) {
+        self.0.marks.push((cells, dot));
+    }
+
+    /// Builder-style [`add`](Self::add)
+    pub fn with(mut self, dot: Dot, cells: Pair) -> Self {
+        self.add(dot, cells);
+        self
+    }
+
+    /// Every dot is given, so orthogonal neighbours without a dot are
+    /// neither consecutive nor in a 1:2 ratio
+    pub fn all_given(mut self) -> Self {
+        self.0.all_given = true;
+        self
+    }
+
+    pub fn dots(&self) -> impl Iterator<Item = (Pair, Dot)> {
+        self.0.marks.iter().copied()
+    }
+}
+
+impl Constraint for Kropki {
+    fn name(&self) -> &str {
+        "Kropki"
+    }
+
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        self.0.propagate(sp)
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        self.0.is_satisfied(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PossibleValues, Puzzle};
+
+    #[test]
+    fn dots_between_cells() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.grid[0][0] = PossibleValues::from(Digit::unchecked(4));
+        let kropki = Kropki::new()
+            .with(Dot::Black, ((0, 0), (0, 1)))
+            .with(Dot::White, ((0, 0), (1, 0)));
+        assert!(kropki.propagate(&mut sp));
+        assert_eq!(sp.grid[0][1].options(), [2, 8].map(Digit::unchecked));
+        assert_eq!(sp.grid[1][0].options(), [3, 5].map(Digit::unchecked));
+        assert!(!kropki.propagate(&mut sp));
+
+        // Without a dot, the neighbours of the 4 are neither 2, 3, 5 nor 8
+        let negative = kropki.clone().all_given();
+        assert!(negative.propagate(&mut sp));
+        assert_eq!(sp.grid[0][1].options(), [2, 8].map(Digit::unchecked));
+        assert_eq!(
+            sp.grid[2][0].options(),
+            [1, 2, 3, 5, 7, 8, 9].map(Digit::unchecked)
+        );
+        sp.grid[0][1] = PossibleValues::from(Digit::unchecked(2));
+        negative.propagate(&mut sp);
+        assert_eq!(
+            sp.grid[0][2].options(),
+            [2, 5, 6, 7, 8, 9].map(Digit::unchecked)
+        );
+    }
+
+    #[test]
+    fn solve_kropki() {
+        let data = std::fs::read_to_string("puzzles/variants/kropki.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        // With every dot given, no digits are needed
+        assert!(puzzle.sudoku.iter().all(Option::is_none));
+        let solution = puzzle.solve().unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{Digit, Grid, SudokuPossibilities};
+
+use super::Constraint;
+
+/// Little Killer: the digits along a diagonal, pointed at by a clue outside
+/// the grid, add up to its sum. Digits may repeat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LittleKiller {
+    sum: u32,
+    cells: Vec<(usize, usize)>,
+}
+
+impl LittleKiller {
+    /// The diagonal from `start` to the edge of the grid, moving by `step`
+    /// such as `(1, 1)` for down and to the right. Returns `None` if `start`
+    /// is not in the grid or `step` is not diagonal.
+    pub fn new(sum: u32, start: (usize, usize), step: (isize, isize)) -> Option<Self> {
+        if start.0 >= 9 || start.1 >= 9 || step.0.abs() != 1 || step.1.abs() != 1 {
+            return None;
+        }
+        let cells = std::iter::successors(Some(start), |&(r, c)| {
+            Some((r.checked_add_signed(step.0)?, c.checked_add_signed(step.1)?))
+        })
+        .take_while(|&(r, c)| r < 9 && c < 9)
+        .collect();
+        Some(Self { sum, cells })
+    }
+
+    pub fn sum(&self) -> u32 {
+        self.sum
+    }
+
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+}
+
+fn value(digit: Digit) -> u32 {
+    u32::from(digit.index()) + 1
+}
+
+impl Constraint for LittleKiller {
+    fn name(&self) -> &str {
+        "Little Killer"
+    }
+
+    /// Each digit must leave a sum for the other cells between the smallest
+    /// and the largest they can add up to
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let bounds: Option<Vec<_>> = self
+            .cells
+            .iter()
+            .map(|&(r, c)| {
+                let candidates = sp.grid[r][c];
+                Some((
+                    value(candidates.iter().next()?),
+                    value(candidates.iter().last()?),
+                ))
+            })
+            .collect();
+        let Some(bounds) = bounds else {
+            return false;
+        };
+        let min: u32 = bounds.iter().map(|&(min, _)| min).sum();
+        let max: u32 = bounds.iter().map(|&(_, max)| max).sum();
+
+        let mut progress = false;
+        for (&(r, c), (own_min, own_max)) in self.cells.iter().zip(bounds) {
+            let (others_min, others_max) = (min - own_min, max - own_max);
+            for digit in sp.grid[r][c] {
+                let total = value(digit);
+                if total + others_min > self.sum || total + others_max < self.sum {
+                    sp.grid[r][c].remove(digit);
+                    progress = true;
+                }
+            }
+        }
+        progress
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        let total: u32 = self
+            .cells
+            .iter()
+            .map(|&(r, c)| value(grid.grid[r][c]))
+            .sum();
+        total == self.sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Puzzle, SolverConfig};
+
+    #[test]
+    fn bounds_along_the_diagonal() {
+        let clue = LittleKiller::new(6, (6, 0), (1, 1)).unwrap();
+        assert_eq!(clue.cells(), [(6, 0), (7, 1), (8, 2)]);
+        let mut sp = SudokuPossibilities::EMPTY;
+        assert!(clue.propagate(&mut sp));
+        assert_eq!(sp.grid[7][1].options(), [1, 2, 3, 4].map(Digit::unchecked));
+        assert!(!clue.propagate(&mut sp));
+
+        // 25 along three cells needs at least 7 in each
+        let clue = LittleKiller::new(25, (2, 0), (-1, 1)).unwrap();
+        assert_eq!(clue.cells(), [(2, 0), (1, 1), (0, 2)]);
+        assert!(clue.propagate(&mut sp));
+        assert_eq!(sp.grid[1][1].options(), [7, 8, 9].map(Digit::unchecked));
+
+        assert_eq!(LittleKiller::new(6, (0, 0), (0, 1)), None);
+    }
+
+    #[test]
+    fn solve_little_killer() {
+        let data =
+            std::fs::read_to_string("puzzles/variants/little-killer.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let config = SolverConfig {
+            deterministic: false,
+            ..puzzle.config()
+        };
+        let solution = puzzle.sudoku.solve_with(&config).unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{Digit, Grid, PossibleValues, SudokuPossibilities};
+
+use super::Constraint;
+
+/// Magic square: every row, column and main diagonal of a 3×3 box adds up
+/// to 15
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MagicSquare {
+    top_left: (usize, usize),
+}
+
+impl MagicSquare {
+    /// The eight magic squares of the digits 1 to 9, in reading order. They
+    /// are the rotations and reflections of a single one, all with 5 in the
+    /// middle, even digits in the corners and odd digits on the edges.
+    pub const SQUARES: [[u8; 9]; 8] = [
+        [2, 7, 6, 9, 5, 1, 4, 3, 8],
+        [2, 9, 4, 7, 5, 3, 6, 1, 8],
+        [4, 3, 8, 9, 5, 1, 2, 7, 6],
+        [4, 9, 2, 3, 5, 7, 8, 1, 6],
+        [6, 1, 8, 7, 5, 3, 2, 9, 4],
+        [6, 7, 2, 1, 5, 9, 8, 3, 4],
+        [8, 1, 6, 3, 5, 7, 4, 9, 2],
+        [8, 3, 4, 1, 5, 9, 6, 7, 2],
+    ];
+
+    /// The box numbered from 0 to 8 in reading order. Returns `None` for
+    /// other numbers.
+    pub fn new(index: usize) -> Option<Self> {
+        (index < 9).then_some(Self {
+            top_left: (index / 3 * 3, index % 3 * 3),
+        })
+    }
+
+    /// Cells of the box in reading order
+    pub fn cells(&self) -> [(usize, usize); 9] {
+        let (top, left) = self.top_left;
+        std::array::from_fn(|i| (top + i / 3, left + i % 3))
+    }
+}
+
+impl Constraint for MagicSquare {
+    fn name(&self) -> &str {
+        "Magic Square"
+    }
+
+    /// Only eight fillings exist, so each is checked against the candidates
+    /// and the box keeps what the remaining ones use
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let cells = self.cells();
+        let mut supported = [PossibleValues::EMPTY; 9];
+        for square in &Self::SQUARES {
+            let digits = square.map(Digit::unchecked);
+            if cells
+                .iter()
+                .zip(digits)
+                .all(|(&(r, c), digit)| sp.grid[r][c].contains(digit))
+            {
+                for (cell, digit) in supported.iter_mut().zip(digits) {
+                    cell.add(digit);
+                }
+            }
+        }
+        let mut progress = false;
+        for (&(r, c), supported) in cells.iter().zip(supported) {
+            if sp.grid[r][c] & supported != sp.grid[r][c] {
+                sp.grid[r][c] &= supported;
+                progress = true;
+            }
+        }
+        progress
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        let digits = self
+            .cells()
+            .map(|(r, c)| grid.grid[r][c].index() as usize + 1);
+        let lines = [
+            [0, 1, 2],
+            [3, 4, 5],
+            [6, 7, 8],
+            [0, 3, 6],
+            [1, 4, 7],
+            [2, 5, 8],
+            [0, 4, 8],
+            [2, 4, 6],
+        ];
+        lines
+            .iter()
+            .all(|line| line.iter().map(|&i| digits[i]).sum::<usize>() == 15)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Puzzle;
+
+    #[test]
+    fn squares_fix_the_middle() {
+        let magic = MagicSquare::new(4).unwrap();
+        let mut grid = Grid::splat(Digit::unchecked(1));
+        for square in &MagicSquare::SQUARES {
+            for (&(r, c), &digit) in magic.cells().iter().zip(square) {
+                grid.grid[r][c] = Digit::unchecked(digit);
+            }
+            assert!(magic.is_satisfied(&grid));
+        }
+
+        let mut sp = SudokuPossibilities::EMPTY;
+        assert!(magic.propagate(&mut sp));
+        assert_eq!(sp.grid[4][4].determined(), Some(Digit::unchecked(5)));
+        assert_eq!(sp.grid[3][3].options(), [2, 4, 6, 8].map(Digit::unchecked));
+        assert_eq!(sp.grid[3][4].options(), [1, 3, 7, 9].map(Digit::unchecked));
+
+        // A corner and the edge next to it leave a single square
+        sp.grid[3][3] = PossibleValues::from(Digit::unchecked(2));
+        sp.grid[3][4] = PossibleValues::from(Digit::unchecked(7));
+        assert!(magic.propagate(&mut sp));
+        assert_eq!(sp.grid[5][5].determined(), Some(Digit::unchecked(8)));
+        assert!(!magic.propagate(&mut sp));
+
+        assert!(MagicSquare::new(9).is_none());
+    }
+
+    #[test]
+    fn solve_magic_square() {
+        let data =
+            std::fs::read_to_string("puzzles/variants/magic-square.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+}
@@ -0,0 +1,279 @@
+//! Rules that solutions must follow.
+//!
+//! A [`Constraint`] removes the candidates that would break its rule and
+//! checks filled grids against it. Rows, columns and boxes are the three
+//! built-in constraints of every [`ConstraintSet`], and variants add more.
+
+mod anti_king;
+mod arrow;
+mod between;
+mod classic;
+mod clones;
+mod diagonals;
+mod jigsaw;
+mod killer;
+mod kropki;
+mod little_killer;
+mod magic_square;
+mod pairs;
+mod palindrome;
+mod parity;
+mod quadruple;
+mod region;
+mod renban;
+mod sandwich;
+mod sums;
+mod thermometer;
+mod whisper;
+mod windows;
+mod xv;
+
+use std::{fmt, sync::Arc};
+
+use crate::{
+    Bitboard, Digit, Grid, PossibleValues, SudokuPossibilities,
+    unit::{CLASSIC, Unit, Units},
+};
+
+pub use anti_king::AntiKing;
+pub use arrow::Arrow;
+pub use between::Between;
+pub use classic::{Boxes, Columns, Rows};
+pub use clones::Clones;
+pub use diagonals::Diagonals;
+pub use jigsaw::Jigsaw;
+pub use killer::Cage;
+pub use kropki::{Dot, Kropki};
+pub use little_killer::LittleKiller;
+pub use magic_square::MagicSquare;
+pub use pairs::Pair;
+pub use palindrome::Palindrome;
+pub use parity::{OddEven, Parity};
+pub use quadruple::Quadruple;
+pub use region::Region;
+pub use renban::Renban;
+pub use sandwich::Sandwich;
+pub use thermometer::Thermometer;
+pub use whisper::Whisper;
+pub use windows::Windows;
+pub use xv::{Xv, XvMark};
+
+/// A rule that every solution must follow
+pub trait Constraint: Send + Sync {
+    /// Human-readable name, such as "Diagonals"
+    fn name(&self) -> &str;
+
+    /// Remove candidates that would break the rule.
+    /// Returns true if any candidates were removed. When the rule can no
+    /// longer be followed, some cell must be left without candidates.
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool;
+
+    /// [`propagate`](Self::propagate) on a grid whose boxes may have been
+    /// replaced with other regions, as given by `units`. Rules that reason
+    /// about the boxes override this, and the default ignores `units`.
+    fn propagate_in(&self, sp: &mut SudokuPossibilities, _units: &Units) -> bool {
+        self.propagate(sp)
+    }
+
+    /// Whether a filled grid follows the rule
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool;
+}
+
+/// The rules of a puzzle: rows, columns and boxes, and any others added.
+///
+/// The built-in constraints are always part of the set, though the boxes
+/// can be replaced with [`Jigsaw`] regions. Propagation and the strategies
+/// are built around them, so only the added constraints are propagated
+/// separately.
+#[derive(Clone, Default)]
+pub struct ConstraintSet {
+    extra: Vec<Arc<dyn Constraint>>,
+    regions: Option<Arc<Jigsaw>>,
+}
+
+impl ConstraintSet {
+    /// Only the rules of classic sudoku
+    pub fn classic() -> Self {
+        Self::default()
+    }
+
+    /// Add a constraint on top of the ones already in the set
+    pub fn add(&mut self, constraint: impl Constraint + 'static) {
+        self.extra.push(Arc::new(constraint));
+    }
+
+    /// Builder-style [`add`](Self::add)
+    pub fn with(mut self, constraint: impl Constraint + 'static) -> Self {
+        self.add(constraint);
+        self
+    }
+
+    /// Replace the boxes with other regions
+    pub fn set_regions(&mut self, regions: Jigsaw) {
+        self.regions = Some(Arc::new(regions));
+    }
+
+    /// Builder-style [`set_regions`](Self::set_regions)
+    pub fn with_regions(mut self, regions: Jigsaw) -> Self {
+        self.set_regions(regions);
+        self
+    }
+
+    /// The regions that replace the boxes, if any
+    pub fn regions(&self) -> Option<&Jigsaw> {
+        self.regions.as_deref()
+    }
+
+    /// Every constraint, starting with the built-in ones
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Constraint> {
+        let regions = match self.regions() {
+            Some(regions) => regions as &dyn Constraint,
+            None => &Boxes,
+        };
+        [&Rows as &dyn Constraint, &Columns, regions]
+            .into_iter()
+            .chain(self.extra.iter().map(|constraint| constraint.as_ref()))
+    }
+
+    /// Whether there is nothing beyond classic sudoku
+    pub fn is_classic(&self) -> bool {
+        self.extra.is_empty() && self.regions.is_none()
+    }
+
+    /// Rows, columns and regions, with the boxes unless they have been
+    /// replaced
+    pub fn units(&self) -> &Units {
+        self.regions().map_or(&CLASSIC, Jigsaw::units)
+    }
+
+    /// Propagate the added constraints, returning true if any made progress
+    pub(crate) fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let mut progress = false;
+        for constraint in &self.extra {
+            progress |= constraint.propagate_in(sp, self.units());
+        }
+        progress
+    }
+
+    /// Whether a filled grid follows every constraint
+    pub fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        self.iter().all(|constraint| constraint.is_satisfied(grid))
+    }
+}
+
+impl fmt::Debug for ConstraintSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.iter().map(|constraint| constraint.name()))
+            .finish()
+    }
+}
+
+/// Remove the digits of determined cells from the other `cells`, and place
+/// digits that have only one cell left if there are nine of them.
+/// Returns true if any candidates were removed.
+fn all_different(sp: &mut SudokuPossibilities, cells: &[(usize, usize)]) -> bool {
+    let mut progress = false;
+    for &(r, c) in cells {
+        let Some(digit) = sp.grid[r][c].determined() else {
+            continue;
+        };
+        for &(or, oc) in cells {
+            if (or, oc) != (r, c) && sp.grid[or][oc].contains(digit) {
+                sp.grid[or][oc].remove(digit);
+                progress = true;
+            }
+        }
+    }
+    if cells.len() == 9 {
+        for digit in PossibleValues::ANY {
+            let mut places = cells
+                .iter()
+                .filter(|&&(r, c)| sp.grid[r][c].contains(digit));
+            if let (Some(&(r, c)), None) = (places.next(), places.next())
+                && sp.grid[r][c].determined().is_none()
+            {
+                sp.grid[r][c] = PossibleValues::from(digit);
+                progress = true;
+            }
+        }
+    }
+    progress
+}
+
+/// Locked candidates between a region holding every digit once and the
+/// rows, columns and regions of `units`: when a digit of one can only go
+/// where it overlaps the other, it is removed from the rest of the other.
+/// Returns true if any candidates were removed.
+fn locked_candidates(sp: &mut SudokuPossibilities, region: Bitboard, units: &Units) -> bool {
+    let boards = Unit::ALL.map(|unit| units.board(unit));
+    let mut progress = false;
+    for digit in PossibleValues::ANY {
+        let mut positions = sp.positions(digit);
+        for unit in boards {
+            let in_region = positions & region;
+            let in_unit = positions & unit;
+            let remove = if !in_region.is_empty() && (in_region & !unit).is_empty() {
+                in_unit & !region
+            } else if !in_unit.is_empty() && (in_unit & !region).is_empty() {
+                in_region & !unit
+            } else {
+                continue;
+            };
+            for (r, c) in remove.cells() {
+                sp.grid[r][c].remove(digit);
+                progress = true;
+            }
+            positions &= !remove;
+        }
+    }
+    progress
+}
+
+/// Whether `cells` of a filled grid all hold different digits
+fn all_distinct(grid: &Grid<Digit>, cells: &[(usize, usize)]) -> bool {
+    let mut seen = PossibleValues::EMPTY;
+    cells.iter().all(|&(r, c)| {
+        let digit = grid.grid[r][c];
+        let new = !seen.contains(digit);
+        seen.add(digit);
+        new
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SolverConfig, Sudoku};
+
+    #[test]
+    fn extra_constraint_changes_solution() {
+        let empty = Sudoku::splat(None);
+        let classic = empty.solve().unwrap();
+        assert!(!Diagonals.is_satisfied(classic.grid()));
+
+        let config = SolverConfig {
+            constraints: ConstraintSet::classic().with(Diagonals),
+            ..SolverConfig::basic()
+        };
+        let solution = empty.solve_with(&config).unwrap();
+        assert!(config.constraints.is_satisfied(solution.grid()));
+        assert_eq!(
+            format!("{:?}", config.constraints),
+            r#"["Rows", "Columns", "Boxes", "Diagonals"]"#
+        );
+    }
+
+    #[test]
+    fn all_different_finds_singles() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.grid[0][0] = PossibleValues::from(Digit::unchecked(4));
+        for i in 1..8 {
+            sp.grid[i][i].remove(Digit::unchecked(7));
+        }
+        assert!(Diagonals.propagate(&mut sp));
+        assert!(!sp.grid[5][5].contains(Digit::unchecked(4)));
+        assert_eq!(sp.grid[8][8].determined(), Some(Digit::unchecked(7)));
+        assert!(!Diagonals.propagate(&mut sp));
+    }
+}
@@ -0,0 +1,101 @@
+//! Relations marked between pairs of cells, shared by the constraints that
+//! put dots or letters on the borders between cells.
+
+use crate::{Digit, Grid, PossibleValues, SudokuPossibilities};
+
+/// Two cells, usually orthogonally adjacent
+pub type Pair = ((usize, usize), (usize, usize));
+
+/// Kind of mark between two cells
+pub(super) trait Mark: Copy + 'static {
+    /// Every kind, none of which may hold between unmarked neighbours when
+    /// all marks are given
+    const ALL: &'static [Self];
+
+    /// Whether the digits on both sides fit the mark
+    fn holds(self, a: Digit, b: Digit) -> bool;
+}
+
+/// Marks between pairs of cells, optionally with every mark given
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Marks<M> {
+    pub marks: Vec<(Pair, M)>,
+    pub all_given: bool,
+}
+
+impl<M> Default for Marks<M> {
+    fn default() -> Self {
+        Self {
+            marks: Vec::new(),
+            all_given: false,
+        }
+    }
+}
+
+/// Every pair of orthogonally adjacent cells
+fn neighbours() -> impl Iterator<Item = Pair> {
+    (0..81).flat_map(|i| {
+        let (r, c) = (i / 9, i % 9);
+        [(r, c + 1), (r + 1, c)]
+            .into_iter()
+            .filter(|&(nr, nc)| nr < 9 && nc < 9)
+            .map(move |next| ((r, c), next))
+    })
+}
+
+/// Keep the candidates of each cell that leave the other cell a candidate
+/// with which `allowed` holds. Returns true if any candidates were removed.
+pub(super) fn prune(
+    sp: &mut SudokuPossibilities,
+    (a, b): Pair,
+    allowed: impl Fn(Digit, Digit) -> bool,
+) -> bool {
+    let (first, second) = (sp.grid[a.0][a.1], sp.grid[b.0][b.1]);
+    let mut kept = (PossibleValues::EMPTY, PossibleValues::EMPTY);
+    for x in first {
+        for y in second {
+            if allowed(x, y) {
+                kept.0.add(x);
+                kept.1.add(y);
+            }
+        }
+    }
+    sp.grid[a.0][a.1] = kept.0;
+    sp.grid[b.0][b.1] = kept.1;
+    kept != (first, second)
+}
+
+impl<M: Mark> Marks<M> {
+    fn is_marked(&self, (a, b): Pair) -> bool {
+        self.marks
+            .iter()
+            .any(|&(pair, _)| pair == (a, b) || pair == (b, a))
+    }
+
+    /// Unmarked neighbours, which fit none of the marks if all are given
+    fn unmarked(&self) -> impl Iterator<Item = Pair> {
+        neighbours().filter(|&pair| self.all_given && !self.is_marked(pair))
+    }
+
+    pub fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let mut progress = false;
+        for &(pair, mark) in &self.marks {
+            progress |= prune(sp, pair, |a, b| mark.holds(a, b));
+        }
+        for pair in self.unmarked() {
+            progress |= prune(sp, pair, |a, b| !M::ALL.iter().any(|mark| mark.holds(a, b)));
+        }
+        progress
+    }
+
+    pub fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        let digits = |((ar, ac), (br, bc)): Pair| (grid.grid[ar][ac], grid.grid[br][bc]);
+        self.marks.iter().all(|&(pair, mark)| {
+            let (a, b) = digits(pair);
+            mark.holds(a, b)
+        }) && self.unmarked().all(|pair| {
+            let (a, b) = digits(pair);
+            !M::ALL.iter().any(|mark| mark.holds(a, b))
+        })
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{Digit, Grid, SudokuPossibilities};
+
+use super::{Constraint, pairs::prune};
+
+/// Palindrome: the digits read the same from both ends of the line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palindrome {
+    cells: Vec<(usize, usize)>,
+}
+
+impl Palindrome {
+    /// A line through `cells`. Returns `None` if a cell is listed twice.
+    pub fn new(cells: Vec<(usize, usize)>) -> Option<Self> {
+        let distinct = cells
+            .iter()
+            .enumerate()
+            .all(|(i, cell)| !cells[..i].contains(cell));
+        distinct.then_some(Self { cells })
+    }
+
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+
+    /// Cells at the same distance from either end
+    fn mirrored(&self) -> impl Iterator<Item = ((usize, usize), (usize, usize))> {
+        self.cells
+            .iter()
+            .zip(self.cells.iter().rev())
+            .take(self.cells.len() / 2)
+            .map(|(&a, &b)| (a, b))
+    }
+}
+
+impl Constraint for Palindrome {
+    fn name(&self) -> &str {
+        "Palindrome"
+    }
+
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let mut progress = false;
+        for pair in self.mirrored() {
+            progress |= prune(sp, pair, |a, b| a == b);
+        }
+        progress
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        self.mirrored()
+            .all(|((ar, ac), (br, bc))| grid.grid[ar][ac] == grid.grid[br][bc])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PossibleValues, Puzzle};
+
+    #[test]
+    fn mirrored_cells_match() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let line = Palindrome::new(vec![(0, 0), (1, 1), (2, 2), (3, 3), (4, 4)]).unwrap();
+        sp.grid[0][0] = PossibleValues::from(Digit::unchecked(7));
+        sp.grid[1][1].remove(Digit::unchecked(5));
+        assert!(line.propagate(&mut sp));
+        assert_eq!(sp.grid[4][4], PossibleValues::from(Digit::unchecked(7)));
+        assert!(!sp.grid[3][3].contains(Digit::unchecked(5)));
+        assert_eq!(sp.grid[2][2], PossibleValues::ANY);
+        assert!(!line.propagate(&mut sp));
+    }
+
+    #[test]
+    fn solve_palindrome() {
+        let data =
+            std::fs::read_to_string("puzzles/variants/palindrome.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{Digit, Grid, PossibleValues, SudokuPossibilities};
+
+use super::Constraint;
+
+/// Whether a digit is odd or even
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    Odd,
+    Even,
+}
+
+impl Parity {
+    /// Digits of this parity
+    pub fn mask(self) -> PossibleValues {
+        match self {
+            Self::Odd => PossibleValues::ODD,
+            Self::Even => PossibleValues::EVEN,
+        }
+    }
+}
+
+/// Cells marked to hold only odd or only even digits.
+///
+/// The marks only narrow the initial candidates, so they are applied in
+/// the first round of propagation and never again have an effect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OddEven {
+    parity: Parity,
+    cells: Vec<(usize, usize)>,
+}
+
+impl OddEven {
+    pub fn new(parity: Parity, cells: Vec<(usize, usize)>) -> Self {
+        Self { parity, cells }
+    }
+
+    pub fn parity(&self) -> Parity {
+        self.parity
+    }
+
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+}
+
+impl Constraint for OddEven {
+    fn name(&self) -> &str {
+        match self.parity {
+            Parity::Odd => "Odd",
+            Parity::Even => "Even",
+        }
+    }
+
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let mask = self.parity.mask();
+        let mut progress = false;
+        for &(r, c) in &self.cells {
+            if sp.grid[r][c] & mask != sp.grid[r][c] {
+                sp.grid[r][c] &= mask;
+                progress = true;
+            }
+        }
+        progress
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        let mask = self.parity.mask();
+        self.cells
+            .iter()
+            .all(|&(r, c)| mask.contains(grid.grid[r][c]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Puzzle;
+
+    #[test]
+    fn mask_cells() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let even = OddEven::new(Parity::Even, vec![(0, 0), (4, 4)]);
+        assert!(even.propagate(&mut sp));
+        assert_eq!(sp.grid[4][4].options(), [2, 4, 6, 8].map(Digit::unchecked));
+        assert_eq!(sp.grid[0][1], PossibleValues::ANY);
+        assert!(!even.propagate(&mut sp));
+
+        // A given of the wrong parity breaks the cell
+        sp.grid[2][2] = PossibleValues::from(Digit::unchecked(4));
+        assert!(OddEven::new(Parity::Odd, vec![(2, 2)]).propagate(&mut sp));
+        assert!(sp.grid[2][2].is_broken());
+    }
+
+    #[test]
+    fn solve_odd_even() {
+        let data =
+            std::fs::read_to_string("puzzles/variants/odd-even.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+}
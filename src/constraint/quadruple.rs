@@ -0,0 +1,122 @@
+use crate::{Digit, Grid, PossibleValues, SudokuPossibilities};
+
+use super::Constraint;
+
+/// Quadruple: a circle where four cells meet lists digits that all appear
+/// among those cells, as many times as they are listed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quadruple {
+    cells: [(usize, usize); 4],
+    digits: Vec<Digit>,
+}
+
+impl Quadruple {
+    /// The circle at the bottom right corner of `top_left`. Returns `None`
+    /// if the corner is on the edge of the grid or more than four digits
+    /// are listed.
+    pub fn new((r, c): (usize, usize), digits: Vec<Digit>) -> Option<Self> {
+        if r >= 8 || c >= 8 || digits.len() > 4 {
+            return None;
+        }
+        Some(Self {
+            cells: [(r, c), (r, c + 1), (r + 1, c), (r + 1, c + 1)],
+            digits,
+        })
+    }
+
+    pub fn cells(&self) -> &[(usize, usize); 4] {
+        &self.cells
+    }
+
+    pub fn digits(&self) -> &[Digit] {
+        &self.digits
+    }
+
+    /// Whether the four digits, in the order of the cells, include every
+    /// listed digit and repeat none within a row or column
+    fn allows(&self, filling: [Digit; 4]) -> bool {
+        let lines = [(0, 1), (2, 3), (0, 2), (1, 3)];
+        let mut left = filling.to_vec();
+        lines.iter().all(|&(a, b)| filling[a] != filling[b])
+            && self.digits.iter().all(|digit| {
+                let found = left.iter().position(|d| d == digit);
+                found.map(|i| left.swap_remove(i)).is_some()
+            })
+    }
+}
+
+impl Constraint for Quadruple {
+    fn name(&self) -> &str {
+        "Quadruple"
+    }
+
+    /// Every filling of the four cells is tried, keeping the candidates
+    /// that some allowed filling uses
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let [a, b, c, d] = self.cells.map(|(r, c)| sp.grid[r][c]);
+        let mut supported = [PossibleValues::EMPTY; 4];
+        for da in a {
+            for db in b {
+                for dc in c {
+                    for dd in d {
+                        let filling = [da, db, dc, dd];
+                        if self.allows(filling) {
+                            for (cell, digit) in supported.iter_mut().zip(filling) {
+                                cell.add(digit);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        let mut progress = false;
+        for (&(r, c), supported) in self.cells.iter().zip(supported) {
+            if sp.grid[r][c] & supported != sp.grid[r][c] {
+                sp.grid[r][c] &= supported;
+                progress = true;
+            }
+        }
+        progress
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        self.allows(self.cells.map(|(r, c)| grid.grid[r][c]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Puzzle;
+
+    #[test]
+    fn listed_digits_must_fit() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let digits = [1, 2, 3, 4].map(Digit::unchecked);
+        let quad = Quadruple::new((0, 0), digits.to_vec()).unwrap();
+        assert!(quad.propagate(&mut sp));
+        assert_eq!(sp.grid[1][1].options(), digits);
+
+        // Only one cell is left for the 5
+        let mut sp = SudokuPossibilities::EMPTY;
+        let quad = Quadruple::new((4, 4), vec![Digit::unchecked(5)]).unwrap();
+        for (r, c) in [(4, 4), (4, 5), (5, 4)] {
+            sp.grid[r][c].remove(Digit::unchecked(5));
+        }
+        assert!(quad.propagate(&mut sp));
+        assert_eq!(sp.grid[5][5].determined(), Some(Digit::unchecked(5)));
+        assert!(!quad.propagate(&mut sp));
+
+        assert!(Quadruple::new((8, 0), vec![]).is_none());
+    }
+
+    #[test]
+    fn solve_quadruples() {
+        let data =
+            std::fs::read_to_string("puzzles/variants/quadruple.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{Bitboard, Digit, Grid, SudokuPossibilities, Units};
+
+use super::{Constraint, all_different, all_distinct, locked_candidates};
+
+/// Extra region of nine cells that holds every digit once, for rule sets
+/// without a dedicated constraint. Any number of them can be added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    cells: [(usize, usize); 9],
+}
+
+impl Region {
+    /// Returns `None` unless there are exactly nine different cells
+    pub fn new(cells: Vec<(usize, usize)>) -> Option<Self> {
+        let cells: [(usize, usize); 9] = cells.try_into().ok()?;
+        let distinct = cells
+            .iter()
+            .enumerate()
+            .all(|(i, cell)| !cells[..i].contains(cell));
+        distinct.then_some(Self { cells })
+    }
+
+    pub fn cells(&self) -> &[(usize, usize); 9] {
+        &self.cells
+    }
+}
+
+impl Constraint for Region {
+    fn name(&self) -> &str {
+        "Region"
+    }
+
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        self.propagate_in(sp, Units::classic())
+    }
+
+    fn propagate_in(&self, sp: &mut SudokuPossibilities, units: &Units) -> bool {
+        let region = self
+            .cells
+            .iter()
+            .fold(Bitboard::EMPTY, |board, &cell| board | Bitboard::cell(cell));
+        all_different(sp, &self.cells) | locked_candidates(sp, region, units)
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        all_distinct(grid, &self.cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConstraintSet, Puzzle, SolverConfig, Sudoku, constraint::Windows};
+
+    #[test]
+    fn regions_match_windows() {
+        let data = std::fs::read_to_string("puzzles/variants/windoku.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let constraints = Windows::CELLS
+            .iter()
+            .map(|window| Region::new(window.to_vec()).unwrap())
+            .fold(ConstraintSet::classic(), ConstraintSet::with);
+        let config = SolverConfig {
+            constraints,
+            ..SolverConfig::default()
+        };
+        assert_eq!(puzzle.sudoku.solve_with(&config), puzzle.solve());
+
+        assert!(Region::new(Windows::CELLS[0][..8].to_vec()).is_none());
+        assert!(Region::new(vec![(0, 0); 9]).is_none());
+    }
+
+    #[test]
+    fn region_with_jigsaw() {
+        let data = std::fs::read_to_string("puzzles/variants/jigsaw.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        // The 7 of the second jigsaw region is in the second box, which
+        // has another 7 in the blank cell since the boxes no longer apply
+        let jigsaw = puzzle.constraints.regions().unwrap();
+        let region = Region::new(jigsaw.region(1).to_vec()).unwrap();
+        assert!(region.is_satisfied(solution.grid()));
+        let config = SolverConfig {
+            constraints: puzzle.constraints.clone().with(region),
+            ..SolverConfig::default()
+        };
+        let mut sudoku = Sudoku::from(solution);
+        sudoku.grid[2][5] = None;
+        assert_eq!(sudoku.solve_with(&config), Some(solution));
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{Digit, Grid, PossibleValues, SudokuPossibilities};
+
+use super::{Constraint, all_different, all_distinct};
+
+/// Renban: the line holds a set of consecutive digits in any order, each
+/// at most once
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Renban {
+    cells: Vec<(usize, usize)>,
+}
+
+impl Renban {
+    /// A line through `cells`. Returns `None` if it is longer than 9 cells,
+    /// or if a cell is listed twice.
+    pub fn new(cells: Vec<(usize, usize)>) -> Option<Self> {
+        let distinct = cells
+            .iter()
+            .enumerate()
+            .all(|(i, cell)| !cells[..i].contains(cell));
+        (cells.len() <= 9 && distinct).then_some(Self { cells })
+    }
+
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+
+    /// Runs of as many consecutive digits as there are cells
+    fn windows(&self) -> impl Iterator<Item = PossibleValues> {
+        let run = (1 << self.cells.len()) - 1;
+        (0..=9 - self.cells.len()).map(move |start| PossibleValues::from_bits(run << start))
+    }
+}
+
+impl Constraint for Renban {
+    fn name(&self) -> &str {
+        "Renban"
+    }
+
+    /// A run of digits is possible while every cell has a candidate in it
+    /// and every digit of it fits some cell. Candidates outside all possible
+    /// runs are removed.
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let mut progress = all_different(sp, &self.cells);
+        let candidates: Vec<_> = self.cells.iter().map(|&(r, c)| sp.grid[r][c]).collect();
+        let all = candidates
+            .iter()
+            .fold(PossibleValues::EMPTY, |all, &cell| all | cell);
+        let possible = self
+            .windows()
+            .filter(|&window| {
+                window & all == window
+                    && candidates.iter().all(|&cell| !(cell & window).is_broken())
+            })
+            .fold(PossibleValues::EMPTY, |possible, window| possible | window);
+        for &(r, c) in &self.cells {
+            if sp.grid[r][c] & possible != sp.grid[r][c] {
+                sp.grid[r][c] &= possible;
+                progress = true;
+            }
+        }
+        progress
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        let digits = self.cells.iter().map(|&(r, c)| grid.grid[r][c].index());
+        let (Some(min), Some(max)) = (digits.clone().min(), digits.max()) else {
+            return true;
+        };
+        all_distinct(grid, &self.cells) && usize::from(max - min) + 1 == self.cells.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Puzzle;
+
+    #[test]
+    fn runs_around_known_digits() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let line = Renban::new(vec![(0, 0), (1, 1), (2, 2), (3, 3)]).unwrap();
+        sp.grid[0][0] = PossibleValues::from(Digit::unchecked(2));
+        assert!(line.propagate(&mut sp));
+        // Only 1-4 and 2-5 contain the 2
+        assert_eq!(sp.grid[1][1].options(), [1, 3, 4, 5].map(Digit::unchecked));
+
+        // A cell that is 1 or 9 next to a 2 leaves only 1-3
+        let line = Renban::new(vec![(5, 0), (5, 1), (5, 2)]).unwrap();
+        sp.grid[5][0] = PossibleValues::from(Digit::unchecked(1)) | Digit::unchecked(9).into();
+        sp.grid[5][1] = PossibleValues::from(Digit::unchecked(2));
+        assert!(line.propagate(&mut sp));
+        assert_eq!(sp.grid[5][0].determined(), Some(Digit::unchecked(1)));
+        assert_eq!(sp.grid[5][2].options(), [1, 3].map(Digit::unchecked));
+    }
+
+    #[test]
+    fn solve_renban() {
+        let data = std::fs::read_to_string("puzzles/variants/renban.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+}
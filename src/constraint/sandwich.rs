@@ -0,0 +1,142 @@
+use crate::{Digit, Grid, PossibleValues, SudokuPossibilities, unit::Unit};
+
+use super::{
+    Constraint,
+    sums::{combinations, supported},
+};
+
+const ONE: Digit = Digit::MIN;
+const NINE: Digit = Digit::MAX;
+
+/// Sandwich clue: the digits between the 1 and the 9 of a row or column
+/// add up to `sum`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sandwich {
+    sum: u32,
+    cells: [(usize, usize); 9],
+    /// Combinations of digits from 2 to 8 that add up to the sum, for each
+    /// number of cells between the 1 and the 9
+    combinations: [Vec<PossibleValues>; 8],
+}
+
+impl Sandwich {
+    /// Clue for row `row`, counting from 0
+    pub fn row(row: usize, sum: u32) -> Self {
+        Self::new(Unit::Row(row), sum)
+    }
+
+    /// Clue for column `col`, counting from 0
+    pub fn column(col: usize, sum: u32) -> Self {
+        Self::new(Unit::Col(col), sum)
+    }
+
+    fn new(line: Unit, sum: u32) -> Self {
+        let fillings = !(PossibleValues::from(ONE) | PossibleValues::from(NINE));
+        Self {
+            sum,
+            cells: line.cells(),
+            combinations: std::array::from_fn(|len| combinations(len, sum, fillings)),
+        }
+    }
+
+    pub fn sum(&self) -> u32 {
+        self.sum
+    }
+}
+
+impl Constraint for Sandwich {
+    fn name(&self) -> &str {
+        "Sandwich"
+    }
+
+    /// Tries every placement of the 1 and the 9, keeping the candidates that
+    /// some placement with a valid filling allows
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let candidates = self.cells.map(|(r, c)| sp.grid[r][c]);
+        let crusts = PossibleValues::from(ONE) | PossibleValues::from(NINE);
+        let mut allowed = [PossibleValues::EMPTY; 9];
+        for one in (0..9).filter(|&i| candidates[i].contains(ONE)) {
+            for nine in (0..9).filter(|&i| i != one && candidates[i].contains(NINE)) {
+                let (start, end) = (one.min(nine), one.max(nine));
+                let between = &candidates[start + 1..end];
+                let combinations = &self.combinations[between.len()];
+                let filling = supported(between, combinations);
+                if combinations.is_empty() || filling.iter().any(PossibleValues::is_broken) {
+                    continue;
+                }
+                allowed[one] |= PossibleValues::from(ONE);
+                allowed[nine] |= PossibleValues::from(NINE);
+                for (i, cell) in allowed.iter_mut().enumerate() {
+                    if i < start || i > end {
+                        *cell |= candidates[i] & !crusts;
+                    } else if i > start && i < end {
+                        *cell |= filling[i - start - 1];
+                    }
+                }
+            }
+        }
+        let mut progress = false;
+        for (&(r, c), allowed) in self.cells.iter().zip(allowed) {
+            let cell = &mut sp.grid[r][c];
+            if *cell & allowed != *cell {
+                *cell &= allowed;
+                progress = true;
+            }
+        }
+        progress
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        let digits = self.cells.map(|(r, c)| grid.grid[r][c]);
+        let (Some(one), Some(nine)) = (
+            digits.iter().position(|&digit| digit == ONE),
+            digits.iter().position(|&digit| digit == NINE),
+        ) else {
+            return false;
+        };
+        let between = &digits[one.min(nine) + 1..one.max(nine)];
+        let total: u32 = between
+            .iter()
+            .map(|digit| u32::from(digit.index()) + 1)
+            .sum();
+        total == self.sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Puzzle;
+
+    #[test]
+    fn crusts_at_the_ends() {
+        // 2 + 3 + ... + 8 needs every cell in between
+        let mut sp = SudokuPossibilities::EMPTY;
+        let clue = Sandwich::row(2, 35);
+        assert!(clue.propagate(&mut sp));
+        let crusts = PossibleValues::from(ONE) | PossibleValues::from(NINE);
+        assert_eq!(sp.grid[2][0], crusts);
+        assert_eq!(sp.grid[2][8], crusts);
+        assert_eq!(sp.grid[2][4], !crusts);
+        assert!(!clue.propagate(&mut sp));
+
+        // Nothing in between, so the 9 is next to the 1
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.place((4, 3), ONE);
+        sp.propagate().unwrap();
+        assert!(Sandwich::column(3, 0).propagate(&mut sp));
+        assert!(sp.grid[3][3].contains(NINE));
+        assert!(sp.grid[5][3].contains(NINE));
+        assert!(!sp.grid[6][3].contains(NINE));
+    }
+
+    #[test]
+    fn solve_sandwich() {
+        let data =
+            std::fs::read_to_string("puzzles/variants/sandwich.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+}
@@ -0,0 +1,84 @@
+//! Filling groups of cells with different digits that add up to a sum,
+//! shared by the constraints that are about sums.
+
+use crate::PossibleValues;
+
+/// Sets of `len` different digits from `allowed` that add up to `sum`
+pub(super) fn combinations(len: usize, sum: u32, allowed: PossibleValues) -> Vec<PossibleValues> {
+    (0..1 << 9)
+        .map(PossibleValues::from_bits)
+        .filter(|&digits| digits & allowed == digits)
+        .filter(|&digits| {
+            usize::from(digits.count()) == len
+                && digits
+                    .iter()
+                    .map(|digit| u32::from(digit.index()) + 1)
+                    .sum::<u32>()
+                    == sum
+        })
+        .collect()
+}
+
+/// Candidates of each cell that appear in some way of filling the cells
+/// with one of the `combinations`, a different digit per cell.
+///
+/// For each combination the cells are filled in order, and the digits used
+/// so far are enough to tell whether the rest of the cells can still take
+/// the rest of the combination.
+pub(super) fn supported(
+    candidates: &[PossibleValues],
+    combinations: &[PossibleValues],
+) -> Vec<PossibleValues> {
+    let candidates: Vec<u16> = candidates.iter().map(PossibleValues::bits).collect();
+    let all = candidates.iter().fold(0, |all, &cell| all | cell);
+    let mut supported = vec![0; candidates.len()];
+    for combination in combinations.iter().map(PossibleValues::bits) {
+        if combination & !all != 0 {
+            continue;
+        }
+        let next_digits = |used: u16| combination & candidates[used.count_ones() as usize] & !used;
+
+        // Subsets of the combination from which the cells can be
+        // completed. Supersets come first in descending order.
+        let mut completes = [false; 1 << 9];
+        completes[combination as usize] = true;
+        let mut used = combination;
+        while used != 0 {
+            used = (used - 1) & combination;
+            completes[used as usize] =
+                digits(next_digits(used)).any(|digit| completes[(used | digit) as usize]);
+        }
+        if !completes[0] {
+            continue;
+        }
+
+        // Subsets reachable from the start, going up in ascending order
+        let mut reached = [false; 1 << 9];
+        reached[0] = true;
+        let mut used = 0;
+        while used != combination {
+            if reached[used as usize] {
+                for digit in digits(next_digits(used)) {
+                    if completes[(used | digit) as usize] {
+                        supported[used.count_ones() as usize] |= digit;
+                        reached[(used | digit) as usize] = true;
+                    }
+                }
+            }
+            used = used.wrapping_sub(combination) & combination;
+        }
+    }
+    supported
+        .into_iter()
+        .map(PossibleValues::from_bits)
+        .collect()
+}
+
+/// Single-bit masks of the digits in a mask
+fn digits(mut mask: u16) -> impl Iterator<Item = u16> {
+    std::iter::from_fn(move || {
+        let digit = mask & mask.wrapping_neg();
+        mask &= !digit;
+        (digit != 0).then_some(digit)
+    })
+}
@@ -0,0 +1,106 @@
+use crate::{Digit, Grid, PossibleValues, SudokuPossibilities};
+
+use super::Constraint;
+
+/// Thermometer: digits strictly increase from the bulb along the line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Thermometer {
+    cells: Vec<(usize, usize)>,
+}
+
+impl Thermometer {
+    /// A thermometer through `cells`, bulb first. Returns `None` if it is
+    /// longer than 9 cells, or if a cell is listed twice.
+    pub fn new(cells: Vec<(usize, usize)>) -> Option<Self> {
+        let distinct = cells
+            .iter()
+            .enumerate()
+            .all(|(i, cell)| !cells[..i].contains(cell));
+        (cells.len() <= 9 && distinct).then_some(Self { cells })
+    }
+
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+}
+
+/// Digits greater than `digit`
+fn above(digit: Digit) -> PossibleValues {
+    PossibleValues::from_bits(!0 << (digit.index() + 1))
+}
+
+/// Digits less than `digit`
+fn below(digit: Digit) -> PossibleValues {
+    PossibleValues::from_bits(!(!0 << digit.index()))
+}
+
+impl Constraint for Thermometer {
+    fn name(&self) -> &str {
+        "Thermometer"
+    }
+
+    /// Each cell must be above the smallest candidate of the cell before it
+    /// and below the largest candidate of the cell after it. A pass in each
+    /// direction carries the bounds along the whole thermometer.
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let before: Vec<_> = self.cells.iter().map(|&(r, c)| sp.grid[r][c]).collect();
+        for pair in self.cells.windows(2) {
+            let [(pr, pc), (r, c)] = [pair[0], pair[1]];
+            sp.grid[r][c] &= match sp.grid[pr][pc].iter().next() {
+                Some(min) => above(min),
+                None => PossibleValues::EMPTY,
+            };
+        }
+        for pair in self.cells.windows(2).rev() {
+            let [(r, c), (nr, nc)] = [pair[0], pair[1]];
+            sp.grid[r][c] &= match sp.grid[nr][nc].iter().last() {
+                Some(max) => below(max),
+                None => PossibleValues::EMPTY,
+            };
+        }
+        self.cells
+            .iter()
+            .zip(before)
+            .any(|(&(r, c), before)| sp.grid[r][c] != before)
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        self.cells.windows(2).all(|pair| {
+            let [(r, c), (nr, nc)] = [pair[0], pair[1]];
+            grid.grid[r][c].index() < grid.grid[nr][nc].index()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Puzzle;
+
+    #[test]
+    fn bounds_along_the_line() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let thermo = Thermometer::new(vec![(0, 0), (0, 1), (1, 1), (2, 1)]).unwrap();
+        sp.grid[1][1].remove(Digit::unchecked(2));
+        assert!(thermo.propagate(&mut sp));
+        assert_eq!(sp.grid[0][0].iter().last(), Some(Digit::unchecked(6)));
+        assert_eq!(sp.grid[1][1].iter().next(), Some(Digit::unchecked(3)));
+        assert_eq!(sp.grid[2][1].iter().next(), Some(Digit::unchecked(4)));
+        assert!(!thermo.propagate(&mut sp));
+
+        let long = Thermometer::new((0..9).map(|c| (4, c)).collect()).unwrap();
+        let mut sp = SudokuPossibilities::EMPTY;
+        long.propagate(&mut sp);
+        assert_eq!(sp.grid[4][6].determined(), Some(Digit::unchecked(7)));
+        assert_eq!(Thermometer::new(vec![(0, 0), (0, 1), (0, 0)]), None);
+    }
+
+    #[test]
+    fn solve_thermometers() {
+        let data = std::fs::read_to_string("puzzles/variants/thermo.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+}
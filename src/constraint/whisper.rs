@@ -0,0 +1,117 @@
+use crate::{Digit, Grid, PossibleValues, SudokuPossibilities};
+
+use super::{Constraint, pairs::prune};
+
+/// Digits 1 to 4
+const LOW: PossibleValues = PossibleValues::from_bits(0b0_0000_1111);
+/// Digits 6 to 9
+const HIGH: PossibleValues = PossibleValues::from_bits(0b1_1110_0000);
+
+/// German whisper: neighbours along the line differ by at least 5
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Whisper {
+    cells: Vec<(usize, usize)>,
+}
+
+impl Whisper {
+    /// A line through `cells`. Returns `None` if a cell is listed twice.
+    pub fn new(cells: Vec<(usize, usize)>) -> Option<Self> {
+        let distinct = cells
+            .iter()
+            .enumerate()
+            .all(|(i, cell)| !cells[..i].contains(cell));
+        distinct.then_some(Self { cells })
+    }
+
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+}
+
+fn far_apart(a: Digit, b: Digit) -> bool {
+    a.index().abs_diff(b.index()) >= 5
+}
+
+impl Constraint for Whisper {
+    fn name(&self) -> &str {
+        "German Whisper"
+    }
+
+    /// Besides checking each pair of neighbours, no 5 fits next to any
+    /// digit, so the line alternates between low and high digits. Once one
+    /// cell is known to be either, so is every other cell.
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        let mut progress = false;
+        for pair in self.cells.windows(2) {
+            progress |= prune(sp, (pair[0], pair[1]), far_apart);
+        }
+        if self.cells.len() < 2 {
+            return progress;
+        }
+
+        let known = self.cells.iter().enumerate().find_map(|(i, &(r, c))| {
+            let cell = sp.grid[r][c];
+            if cell & LOW == cell {
+                Some((i, LOW))
+            } else if cell & HIGH == cell {
+                Some((i, HIGH))
+            } else {
+                None
+            }
+        });
+        if let Some((known, side)) = known {
+            for (i, &(r, c)) in self.cells.iter().enumerate() {
+                let same = (i + known) % 2 == 0;
+                let side = if same {
+                    side
+                } else {
+                    PossibleValues::ANY & !side
+                };
+                if sp.grid[r][c] & side != sp.grid[r][c] {
+                    sp.grid[r][c] &= side;
+                    progress = true;
+                }
+            }
+        }
+        progress
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        self.cells.windows(2).all(|pair| {
+            far_apart(
+                grid.grid[pair[0].0][pair[0].1],
+                grid.grid[pair[1].0][pair[1].1],
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Puzzle;
+
+    #[test]
+    fn alternate_high_and_low() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let line = Whisper::new(vec![(0, 0), (0, 1), (1, 2), (2, 2)]).unwrap();
+        assert!(line.propagate(&mut sp));
+        assert!(!sp.grid[1][2].contains(Digit::unchecked(5)));
+
+        // A 3 makes the cells at an odd distance from it high
+        sp.grid[1][2] = PossibleValues::from(Digit::unchecked(3));
+        assert!(line.propagate(&mut sp));
+        assert_eq!(sp.grid[0][0], LOW);
+        assert_eq!(sp.grid[0][1].options(), [8, 9].map(Digit::unchecked));
+        assert_eq!(sp.grid[2][2].options(), [8, 9].map(Digit::unchecked));
+    }
+
+    #[test]
+    fn solve_whisper() {
+        let data = std::fs::read_to_string("puzzles/variants/whisper.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+}
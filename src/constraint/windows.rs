@@ -0,0 +1,105 @@
+use crate::{Bitboard, Digit, Grid, SudokuPossibilities, Units};
+
+use super::{Constraint, all_different, all_distinct, locked_candidates};
+
+/// Four extra 3×3 regions, one row and column in from each corner, that
+/// hold every digit once, as in Windoku
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Windows;
+
+impl Windows {
+    /// Cells of each window, top left first and in reading order
+    pub const CELLS: [[(usize, usize); 9]; 4] = {
+        let mut cells = [[(0, 0); 9]; 4];
+        let mut w = 0;
+        while w < 4 {
+            let (top, left) = (1 + (w / 2) * 4, 1 + (w % 2) * 4);
+            let mut i = 0;
+            while i < 9 {
+                cells[w][i] = (top + i / 3, left + i % 3);
+                i += 1;
+            }
+            w += 1;
+        }
+        cells
+    };
+}
+
+impl Constraint for Windows {
+    fn name(&self) -> &str {
+        "Windows"
+    }
+
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        self.propagate_in(sp, Units::classic())
+    }
+
+    fn propagate_in(&self, sp: &mut SudokuPossibilities, units: &Units) -> bool {
+        let mut progress = false;
+        for window in &Self::CELLS {
+            progress |= all_different(sp, window);
+            let region = window
+                .iter()
+                .fold(Bitboard::EMPTY, |board, &cell| board | Bitboard::cell(cell));
+            progress |= locked_candidates(sp, region, units);
+        }
+        progress
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        Self::CELLS.iter().all(|window| all_distinct(grid, window))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PossibleValues, Puzzle, Unit};
+
+    #[test]
+    fn solve_windoku() {
+        let data = std::fs::read_to_string("puzzles/variants/windoku.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        assert!(Windows.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.is_solution(solution.grid()));
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+
+    #[test]
+    fn window_locks_candidates_in_line() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let six = Digit::unchecked(6);
+        // 6 can only be in row 1 of the top left window
+        for (r, c) in Windows::CELLS[0] {
+            if r != 1 {
+                sp.grid[r][c].remove(six);
+            }
+        }
+        assert!(Windows.propagate(&mut sp));
+        assert!(!sp.grid[1][0].contains(six));
+        assert!(!sp.grid[1][8].contains(six));
+        assert!(sp.grid[1][2].contains(six));
+        assert_eq!(sp.grid[0][0], PossibleValues::ANY);
+    }
+
+    #[test]
+    fn window_locks_candidates_in_regions() {
+        let data = std::fs::read_to_string("puzzles/variants/jigsaw.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let mut sp = SudokuPossibilities::EMPTY;
+        let six = Digit::unchecked(6);
+        // 6 can only be in the top left window within the first box, which
+        // is not a region of the jigsaw
+        for (r, c) in Unit::Box(0).cells() {
+            if !Windows::CELLS[0].contains(&(r, c)) {
+                sp.grid[r][c].remove(six);
+            }
+        }
+        let mut classic = sp;
+        assert!(Windows.propagate(&mut classic));
+        assert!(!classic.grid[3][3].contains(six));
+        assert!(!Windows.propagate_in(&mut sp, puzzle.constraints.units()));
+        assert!(sp.grid[3][3].contains(six));
+    }
+}
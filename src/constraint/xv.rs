@@ -0,0 +1,108 @@
+use crate::{Digit, Grid, SudokuPossibilities};
+
+use super::{
+    Constraint,
+    pairs::{Mark, Marks, Pair},
+};
+
+/// Roman numeral between two cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XvMark {
+    /// The digits add up to 10
+    X,
+    /// The digits add up to 5
+    V,
+}
+
+impl Mark for XvMark {
+    const ALL: &'static [Self] = &[Self::X, Self::V];
+
+    fn holds(self, a: Digit, b: Digit) -> bool {
+        let sum = a.index() + b.index() + 2;
+        match self {
+            Self::X => sum == 10,
+            Self::V => sum == 5,
+        }
+    }
+}
+
+/// X and V marks between pairs of cells
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Xv(Marks<XvMark>);
+
+impl Xv {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Put a mark between two cells
+    pub fn add(&mut self, mark: XvMark, cells: Pair) {
+        self.0.marks.push((cells, mark));
+    }
+
+    /// Builder-style [`add`](Self::add)
+    pub fn with(mut self, mark: XvMark, cells: Pair) -> Self {
+        self.add(mark, cells);
+        self
+    }
+
+    /// Every mark is given, so orthogonal neighbours without one add up to
+    /// neither 5 nor 10
+    pub fn all_given(mut self) -> Self {
+        self.0.all_given = true;
+        self
+    }
+
+    pub fn marks(&self) -> impl Iterator<Item = (Pair, XvMark)> {
+        self.0.marks.iter().copied()
+    }
+}
+
+impl Constraint for Xv {
+    fn name(&self) -> &str {
+        "XV"
+    }
+
+    fn propagate(&self, sp: &mut SudokuPossibilities) -> bool {
+        self.0.propagate(sp)
+    }
+
+    fn is_satisfied(&self, grid: &Grid<Digit>) -> bool {
+        self.0.is_satisfied(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PossibleValues, Puzzle};
+
+    #[test]
+    fn sums_between_cells() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.grid[4][4] = PossibleValues::from(Digit::unchecked(3));
+        let xv = Xv::new()
+            .with(XvMark::V, ((4, 4), (4, 5)))
+            .with(XvMark::X, ((0, 0), (0, 1)));
+        assert!(xv.propagate(&mut sp));
+        assert_eq!(sp.grid[4][5], PossibleValues::from(Digit::unchecked(2)));
+        // Every digit has a partner adding up to 10
+        assert_eq!(sp.grid[0][0], PossibleValues::ANY);
+
+        let negative = xv.all_given();
+        assert!(negative.propagate(&mut sp));
+        assert_eq!(
+            sp.grid[3][4].options(),
+            [1, 3, 4, 5, 6, 8, 9].map(Digit::unchecked)
+        );
+    }
+
+    #[test]
+    fn solve_xv() {
+        let data = std::fs::read_to_string("puzzles/variants/xv.txt").expect("Missing puzzle");
+        let puzzle = Puzzle::parse(&data).unwrap();
+        let solution = puzzle.solve().unwrap();
+        assert!(puzzle.constraints.is_satisfied(solution.grid()));
+        assert!(puzzle.sudoku.count_solutions(2) > 1);
+    }
+}
@@ -1,12 +1,13 @@
 use std::fmt;
 
-/// A single digit in a Sudoku puzzle.
+/// A single digit in a Sudoku puzzle with `N` digits, 9 by default.
+/// Digits above 9 are written as letters, so 10 is `A`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Digit(u8);
+pub struct Digit<const N: usize = 9>(u8);
 
-impl Digit {
+impl<const N: usize> Digit<N> {
     pub const MIN: Self = Self(1);
-    pub const MAX: Self = Self(9);
+    pub const MAX: Self = Self(N as u8);
 
     pub fn new(value: u8) -> Option<Self> {
         if (Self::MIN.0..=Self::MAX.0).contains(&value) {
@@ -21,7 +22,13 @@ impl Digit {
         Self(value)
     }
 
-    /// Index 0..9 for the digit
+    /// The digit written as `c`, either `1` to `9` or a letter from `A`
+    /// onwards in either case
+    pub fn from_char(c: char) -> Option<Self> {
+        Self::new(c.to_digit(36)? as u8)
+    }
+
+    /// Index 0..N for the digit
     pub fn index(self) -> u8 {
         self.0 - 1
     }
@@ -39,8 +46,11 @@ impl Digit {
     }
 }
 
-impl fmt::Display for Digit {
+impl<const N: usize> fmt::Display for Digit<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        match char::from_digit(self.0.into(), 36) {
+            Some(c) => write!(f, "{}", c.to_ascii_uppercase()),
+            None => write!(f, "{}", self.0),
+        }
     }
 }
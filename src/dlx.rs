@@ -0,0 +1,247 @@
+//! Dancing Links (Algorithm X) backend, solving sudoku as an exact cover problem.
+//!
+//! Each of the 729 rows places one digit in one cell, and covers four of
+//! the 324 constraint columns: the cell is filled, and the digit appears in
+//! the row, the column and the box.
+
+use crate::{Digit, Grid, Sudoku, SudokuSolution, unit::box_of};
+
+const COLUMNS: usize = 4 * 81;
+const ROOT: usize = 0;
+
+/// Candidate placement of `digit` at `cell`, as a row index
+fn placement(row: usize) -> ((usize, usize), Digit) {
+    let (cell, digit) = (row / 9, row % 9);
+    (
+        (cell / 9, cell % 9),
+        Digit::from_index(digit as u8).unwrap(),
+    )
+}
+
+/// Constraint columns covered by a placement, numbered from 1
+fn constraints(r: usize, c: usize, d: usize) -> [usize; 4] {
+    [
+        1 + r * 9 + c,
+        1 + 81 + r * 9 + d,
+        1 + 2 * 81 + c * 9 + d,
+        1 + 3 * 81 + box_of((r, c)) * 9 + d,
+    ]
+}
+
+/// Toroidal doubly linked node lists, stored as indices.
+/// Nodes `1..=COLUMNS` are the column headers.
+struct Dlx {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// Column header of each node
+    column: Vec<usize>,
+    /// Matrix row of each node
+    row: Vec<usize>,
+    /// Number of nodes in each column
+    size: Vec<usize>,
+}
+
+impl Dlx {
+    fn new() -> Self {
+        let n = COLUMNS + 1;
+        let mut dlx = Self {
+            left: (0..n).map(|i| (i + n - 1) % n).collect(),
+            right: (0..n).map(|i| (i + 1) % n).collect(),
+            up: (0..n).collect(),
+            down: (0..n).collect(),
+            column: (0..n).collect(),
+            row: vec![usize::MAX; n],
+            size: vec![0; n],
+        };
+        for r in 0..9 {
+            for c in 0..9 {
+                for d in 0..9 {
+                    dlx.add_row((r * 9 + c) * 9 + d, constraints(r, c, d));
+                }
+            }
+        }
+        dlx
+    }
+
+    fn add_row(&mut self, row: usize, columns: [usize; 4]) {
+        let first = self.left.len();
+        for (i, &col) in columns.iter().enumerate() {
+            let node = first + i;
+            self.left.push(if i == 0 { first + 3 } else { node - 1 });
+            self.right.push(if i == 3 { first } else { node + 1 });
+            self.up.push(self.up[col]);
+            self.down.push(col);
+            self.column.push(col);
+            self.row.push(row);
+            let last = self.up[col];
+            self.down[last] = node;
+            self.up[col] = node;
+            self.size[col] += 1;
+        }
+    }
+
+    fn cover(&mut self, col: usize) {
+        let (l, r) = (self.left[col], self.right[col]);
+        self.right[l] = r;
+        self.left[r] = l;
+        let mut i = self.down[col];
+        while i != col {
+            let mut j = self.right[i];
+            while j != i {
+                let (u, d) = (self.up[j], self.down[j]);
+                self.down[u] = d;
+                self.up[d] = u;
+                self.size[self.column[j]] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+
+    fn uncover(&mut self, col: usize) {
+        let mut i = self.up[col];
+        while i != col {
+            let mut j = self.left[i];
+            while j != i {
+                let (u, d) = (self.up[j], self.down[j]);
+                self.down[u] = j;
+                self.up[d] = j;
+                self.size[self.column[j]] += 1;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+        let (l, r) = (self.left[col], self.right[col]);
+        self.right[l] = col;
+        self.left[r] = col;
+    }
+
+    /// Whether the column is still part of the matrix
+    fn is_active(&self, col: usize) -> bool {
+        self.right[self.left[col]] == col
+    }
+
+    /// Node of the given matrix row in its first column
+    fn node_of(&self, row: usize) -> usize {
+        COLUMNS + 1 + row * 4
+    }
+
+    /// Cover all columns of a row chosen up front.
+    /// Returns false if one of them has already been covered.
+    fn select(&mut self, row: usize) -> bool {
+        let node = self.node_of(row);
+        let columns = [node, node + 1, node + 2, node + 3].map(|n| self.column[n]);
+        if !columns.iter().all(|&col| self.is_active(col)) {
+            return false;
+        }
+        for col in columns {
+            self.cover(col);
+        }
+        true
+    }
+
+    /// Find exact covers, calling `found` with the rows of each.
+    /// Stops early once `found` returns false.
+    fn search(&mut self, rows: &mut Vec<usize>, found: &mut impl FnMut(&[usize]) -> bool) -> bool {
+        if self.right[ROOT] == ROOT {
+            return found(rows);
+        }
+
+        // Column with the fewest options
+        let mut col = self.right[ROOT];
+        let mut j = self.right[col];
+        while j != ROOT {
+            if self.size[j] < self.size[col] {
+                col = j;
+            }
+            j = self.right[j];
+        }
+        if self.size[col] == 0 {
+            return true;
+        }
+
+        self.cover(col);
+        let mut i = self.down[col];
+        let mut proceed = true;
+        while i != col && proceed {
+            rows.push(self.row[i]);
+            let mut j = self.right[i];
+            while j != i {
+                self.cover(self.column[j]);
+                j = self.right[j];
+            }
+            proceed = self.search(rows, found);
+            let mut j = self.left[i];
+            while j != i {
+                self.uncover(self.column[j]);
+                j = self.left[j];
+            }
+            rows.pop();
+            i = self.down[i];
+        }
+        self.uncover(col);
+        proceed
+    }
+}
+
+/// Call `found` with each solution of the puzzle, until it returns false
+pub fn solutions_dlx(sudoku: &Sudoku, mut found: impl FnMut(SudokuSolution) -> bool) {
+    let mut dlx = Dlx::new();
+    let mut givens = Vec::new();
+    for (r, row) in sudoku.grid.iter().enumerate() {
+        for (c, cell) in row.iter().enumerate() {
+            if let Some(digit) = cell {
+                let row = (r * 9 + c) * 9 + digit.index() as usize;
+                if !dlx.select(row) {
+                    return;
+                }
+                givens.push(row);
+            }
+        }
+    }
+
+    let mut rows = Vec::with_capacity(81);
+    dlx.search(&mut rows, &mut |rows| {
+        let mut grid = Grid::splat(Digit::MIN);
+        for &row in givens.iter().chain(rows) {
+            let ((r, c), digit) = placement(row);
+            grid.grid[r][c] = digit;
+        }
+        found(SudokuSolution::new(grid).expect("Exact cover is a valid solution"))
+    });
+}
+
+/// First solution found by Dancing Links
+pub fn solve_dlx(sudoku: &Sudoku) -> Option<SudokuSolution> {
+    let mut result = None;
+    solutions_dlx(sudoku, |solution| {
+        result = Some(solution);
+        false
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_grid_has_many_solutions() {
+        let mut count = 0;
+        solutions_dlx(&Sudoku::splat(None), |_| {
+            count += 1;
+            count < 10
+        });
+        assert_eq!(count, 10);
+    }
+
+    #[test]
+    fn conflicting_givens() {
+        let mut sudoku = Sudoku::splat(None);
+        sudoku.grid[0][0] = Some(Digit::unchecked(4));
+        sudoku.grid[1][1] = Some(Digit::unchecked(4));
+        assert_eq!(solve_dlx(&sudoku), None);
+    }
+}
@@ -0,0 +1,214 @@
+//! Solving engines behind a common interface, so they can be swapped.
+
+use std::time::Instant;
+
+use crate::{
+    ConstraintSet, SolveError, SolveOutcome, SolveStats, SolverConfig, Sudoku, SudokuPossibilities,
+    SudokuSolution, dlx,
+};
+
+/// An algorithm that solves puzzles
+pub trait Solver: Send + Sync {
+    /// Short name, as accepted by the `--engine` flag
+    fn name(&self) -> &str;
+
+    /// Find a solution, along with statistics about the work done
+    fn solve(&self, sudoku: &Sudoku) -> Result<SolveOutcome, SolveError>;
+
+    /// Number of solutions, counting no further than `limit`. Fails if the
+    /// search is interrupted before it has counted them, as by a deadline
+    /// or node budget.
+    fn count_solutions(&self, sudoku: &Sudoku, limit: usize) -> Result<usize, SolveError>;
+}
+
+/// Invalid givens are reported the same way by every engine
+fn check_givens(sudoku: &Sudoku, constraints: &ConstraintSet) -> Result<(), SolveError> {
+    sudoku
+        .validate_with(constraints)
+        .map_err(|conflicts| SolveError::InvalidInput {
+            conflict: conflicts[0],
+        })
+}
+
+/// Engines without constraint support refuse puzzles that have more rules,
+/// instead of returning grids that break them
+fn check_classic(constraints: &ConstraintSet) -> Result<(), SolveError> {
+    if constraints.is_classic() {
+        Ok(())
+    } else {
+        Err(SolveError::Unsupported)
+    }
+}
+
+/// Count the solutions passed to `found`, stopping at `limit`
+fn count_until(
+    limit: usize,
+    solutions: impl FnOnce(&mut dyn FnMut(SudokuSolution) -> bool),
+) -> usize {
+    let mut count = 0;
+    if limit > 0 {
+        solutions(&mut |_| {
+            count += 1;
+            count < limit
+        });
+    }
+    count
+}
+
+/// Outcome of an engine that only measures the elapsed time
+fn timed(start: Instant, solution: Option<SudokuSolution>) -> Result<SolveOutcome, SolveError> {
+    Ok(SolveOutcome {
+        solution: solution.ok_or(SolveError::Unsolvable { conflict: None })?,
+        stats: SolveStats {
+            elapsed: start.elapsed(),
+            ..SolveStats::default()
+        },
+    })
+}
+
+/// Strategy-based inference with hypothetical search when it gets stuck
+#[derive(Debug, Clone, Default)]
+pub struct PropagationSolver {
+    pub config: SolverConfig,
+}
+
+impl Solver for PropagationSolver {
+    fn name(&self) -> &str {
+        "propagation"
+    }
+
+    fn solve(&self, sudoku: &Sudoku) -> Result<SolveOutcome, SolveError> {
+        check_givens(sudoku, &self.config.constraints)?;
+        SudokuPossibilities::from(*sudoku).solve_with_stats(&self.config)
+    }
+
+    /// Strategies that assume a unique solution are never used
+    fn count_solutions(&self, sudoku: &Sudoku, limit: usize) -> Result<usize, SolveError> {
+        let mut count = 0;
+        for solution in sudoku.solutions_with(&self.config).take(limit) {
+            solution?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+/// Dancing Links exact cover search. Only solves classic sudoku, and fails
+/// with [`SolveError::Unsupported`] if `constraints` add more rules.
+#[derive(Debug, Clone, Default)]
+pub struct DlxSolver {
+    pub constraints: ConstraintSet,
+}
+
+impl Solver for DlxSolver {
+    fn name(&self) -> &str {
+        "dlx"
+    }
+
+    fn solve(&self, sudoku: &Sudoku) -> Result<SolveOutcome, SolveError> {
+        let start = Instant::now();
+        check_classic(&self.constraints)?;
+        check_givens(sudoku, &self.constraints)?;
+        timed(start, dlx::solve_dlx(sudoku))
+    }
+
+    fn count_solutions(&self, sudoku: &Sudoku, limit: usize) -> Result<usize, SolveError> {
+        check_classic(&self.constraints)?;
+        Ok(count_until(limit, |found| {
+            dlx::solutions_dlx(sudoku, found)
+        }))
+    }
+}
+
+/// Boolean satisfiability solver. Only solves classic sudoku, and fails
+/// with [`SolveError::Unsupported`] if `constraints` add more rules.
+#[cfg(feature = "sat")]
+#[derive(Debug, Clone, Default)]
+pub struct SatSolver {
+    pub constraints: ConstraintSet,
+}
+
+#[cfg(feature = "sat")]
+impl Solver for SatSolver {
+    fn name(&self) -> &str {
+        "sat"
+    }
+
+    fn solve(&self, sudoku: &Sudoku) -> Result<SolveOutcome, SolveError> {
+        let start = Instant::now();
+        check_classic(&self.constraints)?;
+        check_givens(sudoku, &self.constraints)?;
+        timed(start, crate::sat::solve_sat(sudoku))
+    }
+
+    fn count_solutions(&self, sudoku: &Sudoku, limit: usize) -> Result<usize, SolveError> {
+        check_classic(&self.constraints)?;
+        Ok(count_until(limit, |found| {
+            crate::sat::solutions_sat(sudoku, found)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Backend, constraint::Diagonals};
+
+    #[test]
+    fn engines_agree() {
+        let data = std::fs::read_to_string("puzzles/symmetric.txt").expect("Missing puzzle");
+        let sudoku = Sudoku::parse(&data).unwrap();
+        let engines: Vec<Box<dyn Solver>> = vec![
+            Box::new(PropagationSolver::default()),
+            Box::new(DlxSolver::default()),
+            #[cfg(feature = "sat")]
+            Box::new(SatSolver::default()),
+        ];
+        for engine in engines {
+            let outcome = engine.solve(&sudoku).unwrap();
+            assert_eq!(Some(outcome.solution), sudoku.solve(), "{}", engine.name());
+            assert_eq!(
+                engine.count_solutions(&sudoku, 2),
+                Ok(1),
+                "{}",
+                engine.name()
+            );
+        }
+    }
+
+    #[test]
+    fn engines_refuse_variants() {
+        let mut constraints = ConstraintSet::classic();
+        constraints.add(Diagonals);
+        let engines: Vec<Box<dyn Solver>> = vec![
+            Box::new(DlxSolver {
+                constraints: constraints.clone(),
+            }),
+            #[cfg(feature = "sat")]
+            Box::new(SatSolver {
+                constraints: constraints.clone(),
+            }),
+        ];
+        let sudoku = Sudoku::splat(None);
+        for engine in engines {
+            assert_eq!(
+                engine.solve(&sudoku).unwrap_err(),
+                SolveError::Unsupported,
+                "{}",
+                engine.name()
+            );
+            assert_eq!(
+                engine.count_solutions(&sudoku, 2),
+                Err(SolveError::Unsupported),
+                "{}",
+                engine.name()
+            );
+        }
+        let config = SolverConfig {
+            backend: Backend::Dlx,
+            constraints,
+            ..Default::default()
+        };
+        assert_eq!(sudoku.try_solve_with(&config), Err(SolveError::Unsupported));
+    }
+}
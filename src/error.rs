@@ -0,0 +1,148 @@
+use std::fmt;
+
+use crate::{Digit, SudokuPossibilities};
+
+/// Cells that break the rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conflict {
+    /// Every candidate of the cell has been ruled out
+    Empty((usize, usize)),
+    /// Two cells sharing a unit hold the same digit
+    Duplicate {
+        cells: [(usize, usize); 2],
+        digit: Digit,
+    },
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Empty((r, c)) => write!(f, "no candidates left at r{}c{}", r + 1, c + 1),
+            Self::Duplicate {
+                cells: [(ar, ac), (br, bc)],
+                digit,
+            } => write!(
+                f,
+                "r{}c{} and r{}c{} both hold {digit}",
+                ar + 1,
+                ac + 1,
+                br + 1,
+                bc + 1
+            ),
+        }
+    }
+}
+
+/// Why solving failed. Interrupted searches box the state they stopped at,
+/// which keeps the error small enough to pass around cheaply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The givens already break the rules
+    InvalidInput { conflict: Conflict },
+    /// The puzzle has no solution. Contains the conflict inference ran into
+    /// before any guesses were made, if there was one.
+    Unsolvable { conflict: Option<Conflict> },
+    /// The deadline passed before a solution was found.
+    /// Contains the state that was about to be searched.
+    Timeout(Box<SudokuPossibilities>),
+    /// The solve was cancelled through its token.
+    /// Contains the state that was about to be searched.
+    Cancelled(Box<SudokuPossibilities>),
+    /// The search inferred more states than its budget allows.
+    /// Contains the state that was about to be searched.
+    BudgetExceeded(Box<SudokuPossibilities>),
+    /// The search needed more nested guesses than allowed.
+    /// Contains the state that was about to be searched.
+    DepthLimit(Box<SudokuPossibilities>),
+    /// The backend can't check rules beyond those of classic sudoku
+    Unsupported,
+}
+
+impl SolveError {
+    /// State the search stopped at, if it was interrupted rather than
+    /// finding that there is no solution. Solving can be continued from it,
+    /// possibly after placing more clues, although other branches of the
+    /// search are not part of it.
+    pub fn state(&self) -> Option<SudokuPossibilities> {
+        match self {
+            Self::InvalidInput { .. } | Self::Unsolvable { .. } | Self::Unsupported => None,
+            Self::Timeout(state)
+            | Self::Cancelled(state)
+            | Self::BudgetExceeded(state)
+            | Self::DepthLimit(state) => Some(**state),
+        }
+    }
+}
+
+impl From<Conflict> for SolveError {
+    fn from(conflict: Conflict) -> Self {
+        Self::Unsolvable {
+            conflict: Some(conflict),
+        }
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidInput { conflict } => write!(f, "invalid puzzle: {conflict}"),
+            Self::Unsolvable {
+                conflict: Some(conflict),
+            } => write!(f, "puzzle has no solution: {conflict}"),
+            Self::Unsolvable { conflict: None } => write!(f, "puzzle has no solution"),
+            Self::Timeout(_) => write!(f, "ran out of time"),
+            Self::Cancelled(_) => write!(f, "solve was cancelled"),
+            Self::BudgetExceeded(_) => write!(f, "search exceeded its node budget"),
+            Self::DepthLimit(_) => write!(f, "search exceeded its guess depth limit"),
+            Self::Unsupported => write!(f, "engine only supports classic rules"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PossibleValues, SolverConfig, Sudoku};
+
+    #[test]
+    fn resume_from_state() {
+        let config = SolverConfig {
+            max_depth: Some(0),
+            ..SolverConfig::basic()
+        };
+        let error = SudokuPossibilities::EMPTY.solve(&config).unwrap_err();
+        let mut state = error.state().unwrap();
+        assert_eq!(
+            state.candidates((0, 0)),
+            PossibleValues::from(Digit::unchecked(1))
+        );
+        state.place((8, 8), Digit::unchecked(3));
+        let solution = state.solve(&SolverConfig::basic()).unwrap();
+        assert_eq!(solution.get((8, 8)), Digit::unchecked(3));
+        assert_eq!(state.to_sudoku().grid[0][0], Some(Digit::unchecked(1)));
+        assert_eq!(SolveError::Unsolvable { conflict: None }.state(), None);
+    }
+
+    #[test]
+    fn invalid_input_names_cells() {
+        let mut sudoku = Sudoku::splat(None);
+        sudoku.grid[1][1] = Some(Digit::unchecked(4));
+        sudoku.grid[2][0] = Some(Digit::unchecked(4));
+        let error = sudoku.try_solve_with(&Default::default()).unwrap_err();
+        assert_eq!(
+            error,
+            SolveError::InvalidInput {
+                conflict: Conflict::Duplicate {
+                    cells: [(1, 1), (2, 0)],
+                    digit: Digit::unchecked(4),
+                }
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "invalid puzzle: r2c2 and r3c1 both hold 4"
+        );
+    }
+}
@@ -1,63 +1,69 @@
 use std::{fmt, ops};
 
-/// A 9x9 grid
+/// A square grid with `N` rows and columns, 9 by default
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Grid<T> {
-    pub grid: [[T; 9]; 9],
+pub struct Grid<T, const N: usize = 9> {
+    pub grid: [[T; N]; N],
 }
-impl<T> From<[[T; 9]; 9]> for Grid<T> {
-    fn from(grid: [[T; 9]; 9]) -> Self {
+impl<T, const N: usize> From<[[T; N]; N]> for Grid<T, N> {
+    fn from(grid: [[T; N]; N]) -> Self {
         Self { grid }
     }
 }
 
-impl<T: Copy> Grid<T> {
+impl<T: Copy, const N: usize> Grid<T, N> {
     /// Initialize a grid with the same value in all cells
     pub fn splat(empty: T) -> Self {
         Grid {
-            grid: [[empty; 9]; 9],
+            grid: [[empty; N]; N],
         }
     }
 
     /// Apply a function to each cell in the grid, returning a new grid.
-    pub fn map<N: Copy, F: Fn(T) -> N>(self, f: F) -> Grid<N> {
+    pub fn map<U: Copy, F: Fn(T) -> U>(self, f: F) -> Grid<U, N> {
         self.try_map(|v| Some(f(v))).unwrap()
     }
 
     /// Apply a function to each cell in the grid, returning a new grid.
     /// If the function returns None for any cell, the entire operation fails.
-    pub fn try_map<N, F>(self, f: F) -> Option<Grid<N>>
+    pub fn try_map<U, F>(self, f: F) -> Option<Grid<U, N>>
     where
-        N: Copy,
-        F: Fn(T) -> Option<N>,
+        U: Copy,
+        F: Fn(T) -> Option<U>,
     {
         let mut res = Grid::splat(f(self.grid[0][0])?);
-        for i in 0..9 {
-            for j in 0..9 {
-                res.grid[i][j] = f(self.grid[i][j])?;
-            }
+        for (cell, &value) in res.cells_mut().iter_mut().zip(self.cells()) {
+            *cell = f(value)?;
         }
         Some(res)
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &T> {
-        self.grid.iter().flat_map(|row| row.iter())
+        self.cells().iter()
+    }
+
+    /// All cells in row-major order, so cell `(row, col)` is at
+    /// `row * N + col`. The rows are stored back to back, so this is free.
+    pub fn cells(&self) -> &[T] {
+        self.grid.as_flattened()
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        self.grid.as_flattened_mut()
     }
 
     /// Apply a binary operation to each cell in two grids,
     /// combining them into a new grid.
     pub fn binop<F: Fn(T, T) -> T>(self, rhs: Self, op: F) -> Self {
         let mut res = self;
-        for i in 0..9 {
-            for j in 0..9 {
-                res.grid[i][j] = op(self.grid[i][j], rhs.grid[i][j]);
-            }
+        for (cell, &other) in res.cells_mut().iter_mut().zip(rhs.cells()) {
+            *cell = op(*cell, other);
         }
         res
     }
 }
 
-impl<T> ops::BitAnd for Grid<T>
+impl<T, const N: usize> ops::BitAnd for Grid<T, N>
 where
     T: Copy + ops::BitAnd<Output = T>,
 {
@@ -67,7 +73,7 @@ where
         self.binop(rhs, |a, b| a & b)
     }
 }
-impl<T> ops::BitAndAssign for Grid<T>
+impl<T, const N: usize> ops::BitAndAssign for Grid<T, N>
 where
     T: Copy + ops::BitAnd<Output = T>,
 {
@@ -75,7 +81,7 @@ where
         *self = self.binop(rhs, |a, b| a & b);
     }
 }
-impl<T> ops::BitOr for Grid<T>
+impl<T, const N: usize> ops::BitOr for Grid<T, N>
 where
     T: Copy + ops::BitOr<Output = T>,
 {
@@ -85,7 +91,7 @@ where
         self.binop(rhs, |a, b| a | b)
     }
 }
-impl<T> ops::BitOrAssign for Grid<T>
+impl<T, const N: usize> ops::BitOrAssign for Grid<T, N>
 where
     T: Copy + ops::BitOr<Output = T>,
 {
@@ -94,7 +100,7 @@ where
     }
 }
 
-impl<T> fmt::Display for Grid<T>
+impl<T, const N: usize> fmt::Display for Grid<T, N>
 where
     T: fmt::Display,
 {
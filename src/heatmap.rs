@@ -0,0 +1,120 @@
+use std::fmt::{self, Write};
+
+use crate::SudokuPossibilities;
+
+/// Side length of a single cell in the SVG output, in pixels.
+const SVG_CELL: usize = 40;
+
+/// Candidate-count heatmap of a [`SudokuPossibilities`].
+///
+/// The text form (via [`Display`](fmt::Display)) prints the number of
+/// remaining candidates of each cell, with `X` for broken cells.
+pub struct Heatmap<'a>(&'a SudokuPossibilities);
+
+impl SudokuPossibilities {
+    /// Heatmap showing how constrained each cell is
+    pub fn heatmap(&self) -> Heatmap<'_> {
+        Heatmap(self)
+    }
+}
+
+impl Heatmap<'_> {
+    /// Render the heatmap as a standalone SVG image.
+    /// Cells go from green (determined) to red (all nine candidates open).
+    pub fn to_svg(&self) -> String {
+        let size = SVG_CELL * 9;
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 {size} {size}">"#
+        )
+        .unwrap();
+
+        for (ri, row) in self.0.grid.iter().enumerate() {
+            for (ci, cell) in row.iter().enumerate() {
+                let count = cell.count();
+                let fill = if cell.is_broken() {
+                    "black".to_owned()
+                } else {
+                    format!("hsl({}, 75%, 60%)", 120 - (count as usize - 1) * 15)
+                };
+                let (x, y) = (ci * SVG_CELL, ri * SVG_CELL);
+                writeln!(
+                    svg,
+                    r#"<rect x="{x}" y="{y}" width="{SVG_CELL}" height="{SVG_CELL}" fill="{fill}" stroke="gray"/>"#
+                )
+                .unwrap();
+                if !cell.is_broken() {
+                    writeln!(
+                        svg,
+                        r#"<text x="{}" y="{}" font-size="{}" text-anchor="middle" dominant-baseline="central">{count}</text>"#,
+                        x + SVG_CELL / 2,
+                        y + SVG_CELL / 2,
+                        SVG_CELL / 2,
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        for i in (0..=9).step_by(3) {
+            let p = i * SVG_CELL;
+            writeln!(
+                svg,
+                r#"<line x1="{p}" y1="0" x2="{p}" y2="{size}" stroke="black" stroke-width="3"/>"#
+            )
+            .unwrap();
+            writeln!(
+                svg,
+                r#"<line x1="0" y1="{p}" x2="{size}" y2="{p}" stroke="black" stroke-width="3"/>"#
+            )
+            .unwrap();
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+impl fmt::Display for Heatmap<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.0.grid {
+            for cell in row {
+                if cell.is_broken() {
+                    write!(f, "X ")?;
+                } else {
+                    write!(f, "{} ", cell.count())?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Digit, PossibleValues};
+
+    use super::*;
+
+    #[test]
+    fn heatmap_text() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.grid[0][0] = PossibleValues::from(Digit::unchecked(4));
+        sp.grid[0][1].remove(Digit::unchecked(4));
+        sp.grid[0][2] = PossibleValues::EMPTY;
+
+        let text = sp.heatmap().to_string();
+        let first = text.lines().next().unwrap();
+        assert_eq!(first, "1 8 X 9 9 9 9 9 9 ");
+        assert_eq!(text.lines().count(), 9);
+    }
+
+    #[test]
+    fn heatmap_svg() {
+        let svg = SudokuPossibilities::EMPTY.heatmap().to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<rect").count(), 81);
+    }
+}
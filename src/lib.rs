@@ -1,17 +1,60 @@
 #![forbid(unsafe_code)]
 #![deny(unused_must_use)]
-#![allow(clippy::result_large_err)]
 
+use crate::unit::CLASSIC;
+
+mod batch;
+mod bitboard;
 mod bitset;
+mod config;
+pub mod constraint;
 mod digit;
+mod dlx;
+mod engine;
+mod error;
 mod grid;
+mod heatmap;
+mod multi;
+mod puzzle;
+mod rating;
+#[cfg(feature = "sat")]
+mod sat;
+mod search;
+mod sized;
+mod solution;
 mod solver;
+mod stats;
+pub mod strategy;
+mod unit;
 
-use crate::solver::SudokuPossibilities;
-pub use crate::{bitset::PossibleValues, digit::Digit, grid::Grid};
+#[cfg(feature = "parallel")]
+pub use crate::config::Parallelism;
+#[cfg(feature = "sat")]
+pub use crate::engine::SatSolver;
+pub use crate::{
+    batch::{solve_batch, solve_stream},
+    bitboard::Bitboard,
+    bitset::{Bits, Candidates, PossibleValues, Side, SideLength},
+    config::{Backend, CancellationToken, SolverConfig, ValueOrder},
+    constraint::{Constraint, ConstraintSet},
+    digit::Digit,
+    engine::{DlxSolver, PropagationSolver, Solver},
+    error::{Conflict, SolveError},
+    grid::Grid,
+    heatmap::Heatmap,
+    multi::MultiSudoku,
+    puzzle::Puzzle,
+    rating::{GUESS_DIFFICULTY, Rating, SolveReport, StrategyUse},
+    search::{Search, Solutions, Step},
+    sized::{Hexadoku, SizedSolver, SizedSudoku},
+    solution::SudokuSolution,
+    solver::{LogicalOutcome, SudokuPossibilities},
+    stats::{SolveOutcome, SolveStats},
+    strategy::{Contradiction, ForcingChain},
+    unit::{Unit, Units},
+};
 
 pub type Sudoku = Grid<Option<Digit>>;
-pub type SudokuSolution = Grid<Digit>;
 
 impl Sudoku {
     pub fn parse(data: &str) -> Option<Self> {
@@ -32,19 +75,126 @@ impl Sudoku {
         Some(Self { grid })
     }
 
-    pub fn solve(&self) -> Option<Grid<Digit>> {
-        SudokuPossibilities::from(*self).solve().ok()
+    pub fn solve(&self) -> Option<SudokuSolution> {
+        self.solve_with(&SolverConfig::default())
+    }
+
+    pub fn solve_with(&self, config: &SolverConfig) -> Option<SudokuSolution> {
+        self.try_solve_with(config).ok()
+    }
+
+    /// Solve, reporting why no solution was returned
+    pub fn try_solve_with(&self, config: &SolverConfig) -> Result<SudokuSolution, SolveError> {
+        self.solve_with_stats(config)
+            .map(|outcome| outcome.solution)
+    }
+
+    /// Solve, also counting the work done. Only the propagation backend
+    /// counts anything besides the elapsed time.
+    pub fn solve_with_stats(&self, config: &SolverConfig) -> Result<SolveOutcome, SolveError> {
+        config.solver().solve(self)
+    }
+
+    /// Check that no unit has the same digit given twice, listing every
+    /// pair of clashing givens otherwise
+    pub fn validate(&self) -> Result<(), Vec<Conflict>> {
+        self.validate_in(&CLASSIC)
+    }
+
+    /// Like [`validate`](Self::validate), with the regions of `constraints`
+    /// in place of the boxes if it has any
+    pub fn validate_with(&self, constraints: &ConstraintSet) -> Result<(), Vec<Conflict>> {
+        self.validate_in(constraints.units())
+    }
+
+    fn validate_in(&self, units: &Units) -> Result<(), Vec<Conflict>> {
+        let mut conflicts = Vec::new();
+        for unit in Unit::ALL {
+            let mut seen = [None; 9];
+            for (r, c) in units.cells(unit) {
+                let Some(digit) = self.grid[r][c] else {
+                    continue;
+                };
+                if let Some(other) = seen[digit.index() as usize].replace((r, c)) {
+                    let conflict = Conflict::Duplicate {
+                        cells: [other, (r, c)],
+                        digit,
+                    };
+                    // Cells can share both a line and a region
+                    if !conflicts.contains(&conflict) {
+                        conflicts.push(conflict);
+                    }
+                }
+            }
+        }
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(conflicts)
+        }
+    }
+
+    /// Whether `candidate` is a valid completed grid that keeps every given
+    pub fn is_solution(&self, candidate: &Grid<Digit>) -> bool {
+        let keeps_givens = self
+            .iter()
+            .zip(candidate.iter())
+            .all(|(given, digit)| given.is_none_or(|given| given == *digit));
+        keeps_givens && SudokuSolution::new(*candidate).is_some()
+    }
+
+    /// Number of solutions, counting no further than `limit`
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        self.count_solutions_with(&SolverConfig::default(), limit)
+            .expect("Searches without limits run to the end")
+    }
+
+    /// Number of solutions, counting no further than `limit`.
+    /// Strategies that assume a unique solution are never used. Fails if
+    /// the search is cancelled, runs out of time or exceeds its node budget
+    /// or depth limit before it has counted them.
+    pub fn count_solutions_with(
+        &self,
+        config: &SolverConfig,
+        limit: usize,
+    ) -> Result<usize, SolveError> {
+        config.solver().count_solutions(self, limit)
+    }
+
+    /// Whether the puzzle has exactly one solution.
+    /// The search stops as soon as a second solution is found.
+    pub fn has_unique_solution(&self) -> bool {
+        self.has_unique_solution_with(&SolverConfig::default())
+            .expect("Searches without limits run to the end")
+    }
+
+    /// Whether the puzzle has exactly one solution under `config`. Fails
+    /// like [`count_solutions_with`](Self::count_solutions_with) if the
+    /// search stops before it can tell.
+    pub fn has_unique_solution_with(&self, config: &SolverConfig) -> Result<bool, SolveError> {
+        Ok(self.count_solutions_with(config, 2)? == 1)
+    }
+
+    /// Solve using human-style strategies only, without guessing.
+    /// Returns `None` if the puzzle is found to be invalid.
+    pub fn solve_logical(&self) -> Option<LogicalOutcome> {
+        self.solve_logical_with(&SolverConfig::default())
+    }
+
+    pub fn solve_logical_with(&self, config: &SolverConfig) -> Option<LogicalOutcome> {
+        SudokuPossibilities::from(*self).solve_logical(config).ok()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
+    use std::{fs, path::PathBuf};
 
     use super::*;
 
-    #[test]
-    fn solve_examples() {
+    /// All example puzzles in the `puzzles` directory
+    fn examples() -> Vec<(PathBuf, Sudoku)> {
+        let mut result = Vec::new();
         for entry in fs::read_dir("puzzles").expect("Failed to read puzzles directory") {
             let entry = entry.expect("Failed to read entry");
             if entry
@@ -55,7 +205,7 @@ mod tests {
             {
                 continue;
             }
-            let Ok(data) = std::fs::read_to_string(&entry.path()) else {
+            let Ok(data) = std::fs::read_to_string(entry.path()) else {
                 panic!("Failed to read file: {}", entry.path().display());
             };
             let Some(sudoku) = Sudoku::parse(&data) else {
@@ -64,12 +214,280 @@ mod tests {
                     entry.path().display()
                 );
             };
-            if sudoku.solve().is_none() {
-                panic!(
-                    "Failed to solve sudoku from file: {}",
-                    entry.path().display()
+            result.push((entry.path(), sudoku));
+        }
+        result
+    }
+
+    #[test]
+    fn solve_examples() {
+        for (path, sudoku) in examples() {
+            let Some(solution) = sudoku.solve() else {
+                panic!("Failed to solve sudoku from file: {}", path.display());
+            };
+            assert!(sudoku.is_solution(solution.grid()), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn validate_lists_duplicates() {
+        let mut sudoku = Sudoku::splat(None);
+        assert_eq!(sudoku.validate(), Ok(()));
+        let five = Digit::unchecked(5);
+        sudoku.grid[0][0] = Some(five);
+        sudoku.grid[0][1] = Some(five);
+        sudoku.grid[7][1] = Some(five);
+        assert_eq!(
+            sudoku.validate(),
+            Err(vec![
+                Conflict::Duplicate {
+                    cells: [(0, 0), (0, 1)],
+                    digit: five
+                },
+                Conflict::Duplicate {
+                    cells: [(0, 1), (7, 1)],
+                    digit: five
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn reject_bad_solutions() {
+        let data = fs::read_to_string("puzzles/wiki.txt").unwrap();
+        let sudoku = Sudoku::parse(&data).unwrap();
+        let solution = sudoku.solve().unwrap();
+        assert!(sudoku.is_solution(solution.grid()));
+
+        // Swapping two columns keeps every unit valid but breaks the givens
+        let mut swapped = Grid::from(solution);
+        for row in &mut swapped.grid {
+            row.swap(0, 1);
+        }
+        assert!(Sudoku::splat(None).is_solution(&swapped));
+        assert!(!sudoku.is_solution(&swapped));
+
+        // Only the last column is wrong
+        let mut last = Grid::from(solution);
+        last.grid[0][8] = last.grid[0][7];
+        assert!(!Sudoku::splat(None).is_solution(&last));
+    }
+
+    #[test]
+    fn solve_logical_examples() {
+        for (path, sudoku) in examples() {
+            match sudoku.solve_logical() {
+                Some(LogicalOutcome::Solved(solution)) => {
+                    assert_eq!(Some(solution), sudoku.solve(), "{}", path.display())
+                }
+                other => panic!("Not solved logically: {}: {other:?}", path.display()),
+            }
+        }
+
+        let data = fs::read_to_string("puzzles/7gJb9G8fRt.txt").unwrap();
+        let sudoku = Sudoku::parse(&data).unwrap();
+        let Some(LogicalOutcome::Stuck(sp)) = sudoku.solve_logical_with(&SolverConfig::basic())
+        else {
+            panic!("Basic strategies should not solve this");
+        };
+        assert!(!sp.is_broken());
+    }
+
+    #[test]
+    fn solve_examples_basic() {
+        // Fewer strategies means more guessing, but the same solutions
+        for (path, sudoku) in examples() {
+            assert_eq!(
+                sudoku.solve_with(&SolverConfig::basic()),
+                sudoku.solve(),
+                "Different solution for {}",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn solve_examples_any_value_order() {
+        for value_order in [
+            ValueOrder::LeastConstraining,
+            ValueOrder::Frequency,
+            ValueOrder::Random { seed: 42 },
+        ] {
+            let config = SolverConfig {
+                value_order,
+                deterministic: false,
+                ..SolverConfig::basic()
+            };
+            for (path, sudoku) in examples() {
+                assert_eq!(
+                    sudoku.solve_with(&config),
+                    sudoku.solve(),
+                    "Different solution for {} with {value_order:?}",
+                    path.display()
                 );
             }
         }
     }
+
+    #[test]
+    fn deterministic_smallest_solution() {
+        let data = fs::read_to_string("puzzles/wiki.txt").unwrap();
+        let mut sudoku = Sudoku::parse(&data).unwrap();
+        sudoku.grid[0] = [None; 9];
+        sudoku.grid[1] = [None; 9];
+        let mut all = Vec::new();
+        dlx::solutions_dlx(&sudoku, |solution| {
+            all.push(solution);
+            true
+        });
+        assert!(all.len() > 1);
+        let key = |s: &SudokuSolution| s.grid().iter().map(|d| d.index()).collect::<Vec<_>>();
+        let smallest = all.iter().min_by_key(|s| key(s)).copied();
+        assert_eq!(sudoku.solve(), smallest);
+        assert_eq!(sudoku.solve_with(&SolverConfig::basic()), smallest);
+    }
+
+    #[test]
+    fn count_solutions_up_to_limit() {
+        for (path, sudoku) in examples() {
+            assert_eq!(sudoku.count_solutions(10), 1, "{}", path.display());
+            assert!(sudoku.has_unique_solution(), "{}", path.display());
+        }
+
+        let (_, mut sudoku) = examples().pop().unwrap();
+        // Empty a band's worth of cells except for one given per row
+        let mut removed = 0;
+        for r in 0..3 {
+            for c in 1..9 {
+                removed += sudoku.grid[r][c].is_some() as usize;
+                sudoku.grid[r][c] = None;
+            }
+        }
+        assert!(removed > 0);
+        let dlx = SolverConfig {
+            backend: Backend::Dlx,
+            ..Default::default()
+        };
+        let count = sudoku.count_solutions_with(&dlx, 20).unwrap();
+        assert!(count > 2);
+        assert_eq!(sudoku.count_solutions(20), count);
+        assert_eq!(sudoku.count_solutions(2), 2);
+        assert!(!sudoku.has_unique_solution());
+        assert_eq!(sudoku.has_unique_solution_with(&dlx), Ok(false));
+        assert_eq!(sudoku.count_solutions(0), 0);
+
+        sudoku.grid[0][1] = Some(Digit::unchecked(1));
+        sudoku.grid[0][2] = Some(Digit::unchecked(1));
+        assert_eq!(sudoku.count_solutions(10), 0);
+        assert!(!sudoku.has_unique_solution());
+    }
+
+    #[test]
+    fn count_solutions_within_budget() {
+        let config = SolverConfig {
+            max_nodes: Some(20),
+            ..Default::default()
+        };
+        let sudoku = Sudoku::splat(None);
+        assert!(matches!(
+            sudoku.count_solutions_with(&config, 2),
+            Err(SolveError::BudgetExceeded(_))
+        ));
+        let config = SolverConfig {
+            max_depth: Some(3),
+            ..Default::default()
+        };
+        assert!(matches!(
+            sudoku.count_solutions_with(&config, 2),
+            Err(SolveError::DepthLimit(_))
+        ));
+    }
+
+    #[test]
+    fn uniqueness_within_budget() {
+        // Enough nodes for the first solution of an empty grid, but not
+        // for the second
+        let config = SolverConfig {
+            max_nodes: Some(46),
+            ..Default::default()
+        };
+        let sudoku = Sudoku::splat(None);
+        assert_eq!(sudoku.count_solutions_with(&config, 1), Ok(1));
+        assert!(matches!(
+            sudoku.has_unique_solution_with(&config),
+            Err(SolveError::BudgetExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn stats_count_guesses() {
+        let data = fs::read_to_string("puzzles/7gJb9G8fRt.txt").unwrap();
+        let sudoku = Sudoku::parse(&data).unwrap();
+        let logical = sudoku.solve_with_stats(&SolverConfig::default()).unwrap();
+        assert_eq!(Some(logical.solution), sudoku.solve());
+        assert_eq!(logical.stats.guesses, 0);
+        assert_eq!(logical.stats.max_depth, 0);
+        assert!(logical.stats.propagation_rounds > 0);
+
+        let guessed = sudoku.solve_with_stats(&SolverConfig::basic()).unwrap();
+        assert_eq!(guessed.solution, logical.solution);
+        assert!(guessed.stats.guesses > 0);
+        assert!(guessed.stats.max_depth > 0);
+        assert!(guessed.stats.guesses >= guessed.stats.backtracks);
+    }
+
+    #[test]
+    fn solve_examples_dlx() {
+        let config = SolverConfig {
+            backend: Backend::Dlx,
+            ..Default::default()
+        };
+        for (path, sudoku) in examples() {
+            assert_eq!(
+                sudoku.solve_with(&config),
+                sudoku.solve(),
+                "Different solution for {}",
+                path.display()
+            );
+        }
+    }
+
+    #[cfg(feature = "sat")]
+    #[test]
+    fn solve_examples_sat() {
+        let config = SolverConfig {
+            backend: Backend::Sat,
+            ..Default::default()
+        };
+        for (path, sudoku) in examples() {
+            assert_eq!(
+                sudoku.solve_with(&config),
+                sudoku.solve(),
+                "Different solution for {}",
+                path.display()
+            );
+            let mut count = 0;
+            sat::solutions_sat(&sudoku, |_| {
+                count += 1;
+                true
+            });
+            assert_eq!(count, 1, "Not unique: {}", path.display());
+        }
+    }
+
+    #[test]
+    fn solve_examples_assuming_uniqueness() {
+        let config = SolverConfig {
+            assume_unique_solution: true,
+            ..SolverConfig::extended()
+        };
+        for (path, sudoku) in examples() {
+            assert_eq!(
+                sudoku.solve_with(&config),
+                sudoku.solve(),
+                "Different solution for {}",
+                path.display()
+            );
+        }
+    }
 }
@@ -1,17 +1,113 @@
 #![deny(unused_must_use)]
 
-use sudoku_solver::Sudoku;
+#[cfg(feature = "sat")]
+use sudoku_solver::SatSolver;
+use sudoku_solver::{
+    DlxSolver, LogicalOutcome, PropagationSolver, Puzzle, Solver, SolverConfig, SudokuPossibilities,
+};
+
+const USAGE: &str =
+    "usage: solve [--heatmap | --heatmap-svg | --logical | --report | --engine NAME] puzzle.txt";
+
+/// Engines selectable with `--engine`, the default first
+fn engines(config: &SolverConfig) -> Vec<Box<dyn Solver>> {
+    vec![
+        Box::new(PropagationSolver {
+            config: config.clone(),
+        }),
+        Box::new(DlxSolver {
+            constraints: config.constraints.clone(),
+        }),
+        #[cfg(feature = "sat")]
+        Box::new(SatSolver {
+            constraints: config.constraints.clone(),
+        }),
+    ]
+}
 
 fn main() -> Result<(), &'static str> {
-    let Some(path) = std::env::args().nth(1) else {
-        return Err("usage: solve puzzle.txt");
+    let mut heatmap = None;
+    let mut logical = false;
+    let mut report = false;
+    let mut engine_name = None;
+    let mut path = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--heatmap" => heatmap = Some(false),
+            "--heatmap-svg" => heatmap = Some(true),
+            "--logical" => logical = true,
+            "--report" => report = true,
+            "--engine" => engine_name = Some(args.next().ok_or(USAGE)?),
+            _ if arg.starts_with("--") => return Err(USAGE),
+            _ => path = Some(arg),
+        }
+    }
+    let Some(path) = path else {
+        return Err(USAGE);
     };
     let data = std::fs::read_to_string(&path).expect("Failed to read input file");
-    let sudoku = Sudoku::parse(&data).ok_or("Invalid sudoku input")?;
-    if let Some(solved) = sudoku.solve() {
-        print!("{solved}");
-        Ok(())
-    } else {
-        Err("Invalid sudoku, cannot solve")
+    let puzzle = Puzzle::parse(&data).ok_or("Invalid sudoku input")?;
+    let sudoku = puzzle.sudoku;
+    let config = puzzle.config();
+    if let Err(conflicts) = sudoku.validate_with(&config.constraints) {
+        for conflict in conflicts {
+            eprintln!("{conflict}");
+        }
+        return Err("Invalid sudoku input");
+    }
+
+    if let Some(svg) = heatmap {
+        let mut sp = SudokuPossibilities::from(sudoku);
+        sp.infer(&config).map_err(|_| "Sudoku has no solution")?;
+        if svg {
+            print!("{}", sp.heatmap().to_svg());
+        } else {
+            print!("{}", sp.heatmap());
+        }
+        return Ok(());
+    }
+
+    if logical {
+        match sudoku.solve_logical_with(&config) {
+            Some(LogicalOutcome::Solved(solved)) => print!("{solved}"),
+            Some(LogicalOutcome::Stuck(sp)) => {
+                eprintln!("Cannot solve without guessing, stuck at:");
+                print!("{sp}");
+            }
+            None => return Err("Sudoku has no solution"),
+        }
+        return Ok(());
+    }
+
+    if report {
+        let report = sudoku
+            .solve_with_report(&config)
+            .ok_or("Sudoku has no solution")?;
+        print!("{}", report.solution);
+        eprint!("{}", report.rating);
+        return Ok(());
+    }
+
+    let mut engines = engines(&config);
+    let engine = match engine_name {
+        Some(name) => engines
+            .into_iter()
+            .find(|engine| engine.name() == name)
+            .ok_or("Unknown engine")?,
+        None => engines.remove(0),
+    };
+    if !config.constraints.is_classic() && engine.name() != "propagation" {
+        return Err("Only the propagation engine supports variant rules");
+    }
+    match engine.solve(&sudoku) {
+        Ok(outcome) => {
+            print!("{}", outcome.solution);
+            Ok(())
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            Err("Sudoku has no solution")
+        }
     }
 }
@@ -0,0 +1,222 @@
+use crate::{
+    Conflict, Digit, SolverConfig, Sudoku, SudokuPossibilities, SudokuSolution, ValueOrder,
+};
+
+/// A shared cell, as the index of a grid and the position within it
+type Link = [(usize, (usize, usize)); 2];
+
+/// Several 9×9 grids placed on a larger plane, as in samurai sudoku. Grids
+/// that overlap share whole boxes, and each grid follows the rules on its
+/// own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiSudoku {
+    grids: Vec<((usize, usize), Sudoku)>,
+    /// Every pair of cells in different grids that are the same cell of the plane
+    links: Vec<Link>,
+}
+
+impl MultiSudoku {
+    /// Top left corners of the five grids of a samurai sudoku on its 21×21
+    /// plane, with the middle grid sharing a corner box with each of the others
+    pub const SAMURAI: [(usize, usize); 5] = [(0, 0), (0, 12), (6, 6), (12, 0), (12, 12)];
+
+    /// Grids with their top left corners on the plane. Returns `None` if a
+    /// corner is not on a box boundary, so that overlaps would split boxes,
+    /// or if overlapping grids give different digits for a shared cell.
+    pub fn new(grids: Vec<((usize, usize), Sudoku)>) -> Option<Self> {
+        if grids
+            .iter()
+            .any(|&((top, left), _)| top % 3 != 0 || left % 3 != 0)
+        {
+            return None;
+        }
+        let mut links = Vec::new();
+        for (a, &((a_top, a_left), _)) in grids.iter().enumerate() {
+            for (b, &((b_top, b_left), _)) in grids.iter().enumerate().skip(a + 1) {
+                for i in 0..81 {
+                    let (top, left) = (a_top + i / 9, a_left + i % 9);
+                    if (b_top..b_top + 9).contains(&top) && (b_left..b_left + 9).contains(&left) {
+                        links.push([(a, (i / 9, i % 9)), (b, (top - b_top, left - b_left))]);
+                    }
+                }
+            }
+        }
+        let agree = links.iter().all(|&[(a, (ar, ac)), (b, (br, bc))]| {
+            match (grids[a].1.grid[ar][ac], grids[b].1.grid[br][bc]) {
+                (Some(x), Some(y)) => x == y,
+                _ => true,
+            }
+        });
+        agree.then_some(Self { grids, links })
+    }
+
+    /// Parse the plane as lines of cells, in the format of
+    /// [`Sudoku::parse`], and cut out the grids with their top left corners
+    /// at `corners`. Cells outside every grid are ignored, so they can be
+    /// left blank.
+    pub fn parse(data: &str, corners: &[(usize, usize)]) -> Option<Self> {
+        let plane: Vec<Vec<char>> = data.lines().map(|line| line.chars().collect()).collect();
+        let cell = |r: usize, c: usize| -> Option<Option<Digit>> {
+            match plane.get(r).and_then(|row| row.get(c)) {
+                None | Some('.' | ' ') => Some(None),
+                Some(ch) => Some(Some(Digit::new(ch.to_digit(10)? as u8)?)),
+            }
+        };
+        let grids = corners
+            .iter()
+            .map(|&(top, left)| {
+                let mut sudoku = Sudoku::splat(None);
+                for (i, given) in sudoku.cells_mut().iter_mut().enumerate() {
+                    *given = cell(top + i / 9, left + i % 9)?;
+                }
+                Some(((top, left), sudoku))
+            })
+            .collect::<Option<_>>()?;
+        Self::new(grids)
+    }
+
+    /// Parse a samurai sudoku, laid out on a 21×21 plane
+    pub fn parse_samurai(data: &str) -> Option<Self> {
+        Self::parse(data, &Self::SAMURAI)
+    }
+
+    /// The grids with their top left corners
+    pub fn grids(&self) -> &[((usize, usize), Sudoku)] {
+        &self.grids
+    }
+
+    pub fn solve(&self) -> Option<Vec<SudokuSolution>> {
+        self.solve_with(&SolverConfig::default())
+    }
+
+    /// A solution of every grid, in the order of [`grids`](Self::grids).
+    /// The grids are inferred with `config` and pass the candidates of
+    /// their shared cells to each other until none changes, and guesses go
+    /// to the cell with the fewest candidates in any grid. Only the rules
+    /// and the value order of `config` are used beyond that.
+    pub fn solve_with(&self, config: &SolverConfig) -> Option<Vec<SudokuSolution>> {
+        let mut found = Vec::new();
+        self.search(self.initial(), config, 1, &mut found);
+        found.pop()
+    }
+
+    /// Number of solutions, counting no further than `limit`
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        self.count_solutions_with(&SolverConfig::default(), limit)
+    }
+
+    pub fn count_solutions_with(&self, config: &SolverConfig, limit: usize) -> usize {
+        let mut found = Vec::new();
+        self.search(self.initial(), config, limit, &mut found);
+        found.len()
+    }
+
+    fn initial(&self) -> Vec<SudokuPossibilities> {
+        self.grids
+            .iter()
+            .map(|&(_, sudoku)| SudokuPossibilities::from(sudoku))
+            .collect()
+    }
+
+    /// Infer every grid, narrowing each shared cell to the candidates both
+    /// of its grids allow, until no grid changes
+    fn infer(
+        &self,
+        states: &mut [SudokuPossibilities],
+        config: &SolverConfig,
+    ) -> Result<(), Conflict> {
+        loop {
+            for state in states.iter_mut() {
+                state.infer(config)?;
+            }
+            let mut progress = false;
+            for &[(a, a_cell), (b, b_cell)] in &self.links {
+                let shared = states[a].candidates(a_cell) & states[b].candidates(b_cell);
+                for (grid, (r, c)) in [(a, a_cell), (b, b_cell)] {
+                    if states[grid].grid[r][c] != shared {
+                        states[grid].grid[r][c] = shared;
+                        progress = true;
+                    }
+                }
+            }
+            if !progress {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Depth-first search collecting up to `limit` solutions into `found`
+    fn search(
+        &self,
+        mut states: Vec<SudokuPossibilities>,
+        config: &SolverConfig,
+        limit: usize,
+        found: &mut Vec<Vec<SudokuSolution>>,
+    ) {
+        if found.len() >= limit || self.infer(&mut states, config).is_err() {
+            return;
+        }
+        let guess = states
+            .iter()
+            .enumerate()
+            .filter_map(|(grid, state)| Some((grid, state.branch_cell()?)))
+            .min_by_key(|&(grid, (r, c))| states[grid].grid[r][c].count());
+        let Some((grid, cell)) = guess else {
+            let solutions = states.iter().map(|state| state.solution(config));
+            found.extend(solutions.collect::<Option<Vec<_>>>());
+            return;
+        };
+        let order = if config.deterministic {
+            ValueOrder::Ascending
+        } else {
+            config.value_order
+        };
+        for &digit in states[grid].ordered_values(cell, order).iter() {
+            let mut branch = states.clone();
+            branch[grid].place(cell, digit);
+            self.search(branch, config, limit, found);
+            if found.len() >= limit {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_cells_link_grids() {
+        let data = std::fs::read_to_string("puzzles/wiki.txt").expect("Missing puzzle");
+        let solved = Sudoku::from(Sudoku::parse(&data).unwrap().solve().unwrap());
+        let multi =
+            MultiSudoku::new(vec![((0, 0), solved), ((6, 6), Sudoku::splat(None))]).unwrap();
+        let mut states = multi.initial();
+        multi.infer(&mut states, &SolverConfig::default()).unwrap();
+        // The bottom right box of the solved grid carries over
+        for (r, c) in [(0, 0), (1, 2), (2, 1)] {
+            let digit = solved.grid[r + 6][c + 6];
+            assert_eq!(states[1].grid[r][c].determined(), digit);
+        }
+
+        assert!(MultiSudoku::new(vec![((0, 0), solved), ((4, 4), Sudoku::splat(None))]).is_none());
+        let mut clash = Sudoku::splat(None);
+        clash.grid[0][0] = solved.grid[6][7];
+        assert!(MultiSudoku::new(vec![((0, 0), solved), ((6, 6), clash)]).is_none());
+    }
+
+    #[test]
+    fn solve_samurai() {
+        let data = std::fs::read_to_string("puzzles/multi/samurai.txt").expect("Missing puzzle");
+        let multi = MultiSudoku::parse_samurai(&data).unwrap();
+        let solutions = multi.solve().unwrap();
+        for (&(_, sudoku), solution) in multi.grids().iter().zip(&solutions) {
+            assert!(sudoku.is_solution(solution.grid()));
+        }
+        for &[(a, a_cell), (b, b_cell)] in &multi.links {
+            assert_eq!(solutions[a].get(a_cell), solutions[b].get(b_cell));
+        }
+        assert_eq!(multi.count_solutions(2), 1);
+    }
+}
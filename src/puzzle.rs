@@ -0,0 +1,202 @@
+use crate::{
+    ConstraintSet, Digit, SolverConfig, Sudoku, SudokuSolution,
+    constraint::{
+        AntiKing, Arrow, Between, Cage, Clones, Diagonals, Dot, Jigsaw, Kropki, LittleKiller,
+        MagicSquare, OddEven, Pair, Palindrome, Parity, Quadruple, Region, Renban, Sandwich,
+        Thermometer, Whisper, Windows, Xv, XvMark,
+    },
+};
+
+/// A grid of givens together with the rules it is solved by
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    pub sudoku: Sudoku,
+    pub constraints: ConstraintSet,
+}
+
+impl Puzzle {
+    /// Parse a puzzle. Lines starting with a letter name rules beyond
+    /// classic sudoku, and the other lines hold the grid as in
+    /// [`Sudoku::parse`]. The rules are:
+    ///
+    /// - `diagonals`: both main diagonals hold every digit once
+    /// - `windoku`: four extra 3×3 windows hold every digit once
+    /// - `anti-king`: diagonally adjacent cells hold different digits
+    /// - `arrow PILL... > CELL...`: the digits on the arrow add up to the
+    ///   number read from its circle or pill, such as `arrow r1c1 > r2c2 r3c3`
+    /// - `between CELL...`: the digits on the line are strictly between
+    ///   those in the circles at its ends
+    /// - `cage SUM CELL...`: a killer cage, such as `cage 10 r1c1 r1c2 r2c1`
+    /// - `clone CELL... = CELL...`: both regions hold the same digits in
+    ///   corresponding cells, such as `clone r1c1 r1c2 = r5c5 r5c6`
+    /// - `jigsaw ROW...`: nine regions replace the boxes, given as nine rows
+    ///   of nine characters with one character per region, such as
+    ///   `jigsaw 111222333 111222333 ...`
+    /// - `little-killer SUM CELL DIRECTION`: the digits along the diagonal
+    ///   from `CELL` towards `ne`, `se`, `sw` or `nw` add up to `SUM`,
+    ///   such as `little-killer 23 r1c3 se`
+    /// - `magic-square BOX`: the rows, columns and diagonals of the box,
+    ///   numbered from 1 to 9 in reading order, add up to 15
+    /// - `odd CELL...` and `even CELL...`: the cells hold only odd or even
+    ///   digits
+    /// - `palindrome CELL...`: the digits read the same from both ends
+    /// - `quad CELL DIGITS`: the digits all appear in the 2×2 cells with
+    ///   `CELL` at the top left, such as `quad r1c1 129`
+    /// - `region CELL...`: nine more cells that hold every digit once
+    /// - `renban CELL...`: the line holds consecutive digits in any order
+    /// - `sandwich LINE SUM`: the digits between the 1 and the 9 of a row
+    ///   or column add up to `SUM`, such as `sandwich r3 15` or `sandwich c1 0`
+    /// - `whisper CELL...`: neighbours on the line differ by at least 5
+    /// - `white CELL CELL` and `black CELL CELL`: Kropki dots, for digits that
+    ///   are consecutive or in a 1:2 ratio, such as `white r1c1 r1c2`
+    /// - `all-dots`: every Kropki dot is given
+    /// - `x CELL CELL` and `v CELL CELL`: the digits add up to 10 or 5
+    /// - `all-xv`: every X and V is given
+    /// - `thermo CELL...`: digits increase from the bulb, which is listed
+    ///   first, such as `thermo r1c1 r2c2 r3c3`
+    ///
+    /// Returns `None` for unknown rules.
+    pub fn parse(data: &str) -> Option<Self> {
+        let mut constraints = ConstraintSet::classic();
+        let mut grid = Vec::new();
+        let mut kropki: Option<Kropki> = None;
+        let mut xv: Option<Xv> = None;
+        for line in data.lines() {
+            if !line.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                grid.push(line);
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            let rule = words.next()?;
+            match rule {
+                "diagonals" => constraints.add(Diagonals),
+                "windoku" => constraints.add(Windows),
+                "anti-king" => constraints.add(AntiKing),
+                "arrow" => {
+                    let pill = parse_cells(words.by_ref().take_while(|&word| word != ">"))?;
+                    constraints.add(Arrow::new(pill, parse_cells(words)?)?);
+                }
+                "between" => constraints.add(Between::new(parse_cells(words)?)?),
+                "cage" => {
+                    let sum = words.next()?.parse().ok()?;
+                    constraints.add(Cage::new(sum, parse_cells(words)?)?);
+                }
+                "thermo" => constraints.add(Thermometer::new(parse_cells(words)?)?),
+                "clone" => {
+                    let a = parse_cells(words.by_ref().take_while(|&word| word != "="))?;
+                    constraints.add(Clones::new(a, parse_cells(words)?)?);
+                }
+                "jigsaw" => constraints.set_regions(Jigsaw::parse(words)?),
+                "little-killer" => {
+                    let sum = words.next()?.parse().ok()?;
+                    let start = parse_cell(words.next()?)?;
+                    let step = match words.next()? {
+                        "ne" => (-1, 1),
+                        "se" => (1, 1),
+                        "sw" => (1, -1),
+                        "nw" => (-1, -1),
+                        _ => return None,
+                    };
+                    constraints.add(LittleKiller::new(sum, start, step)?);
+                }
+                "magic-square" => {
+                    constraints.add(MagicSquare::new(parse_index(words.next()?)?)?);
+                }
+                "odd" => constraints.add(OddEven::new(Parity::Odd, parse_cells(words)?)),
+                "even" => constraints.add(OddEven::new(Parity::Even, parse_cells(words)?)),
+                "palindrome" => constraints.add(Palindrome::new(parse_cells(words)?)?),
+                "quad" => {
+                    let cell = parse_cell(words.next()?)?;
+                    let digits = words
+                        .next()?
+                        .chars()
+                        .map(|c| Digit::new(c.to_digit(10)? as u8));
+                    constraints.add(Quadruple::new(cell, digits.collect::<Option<_>>()?)?);
+                }
+                "region" => constraints.add(Region::new(parse_cells(words)?)?),
+                "renban" => constraints.add(Renban::new(parse_cells(words)?)?),
+                "sandwich" => {
+                    let line = words.next()?;
+                    let sum = words.next()?.parse().ok()?;
+                    constraints.add(if let Some(row) = line.strip_prefix('r') {
+                        Sandwich::row(parse_index(row)?, sum)
+                    } else {
+                        Sandwich::column(parse_index(line.strip_prefix('c')?)?, sum)
+                    });
+                }
+                "whisper" => constraints.add(Whisper::new(parse_cells(words)?)?),
+                "white" | "black" => {
+                    let dot = if rule == "white" {
+                        Dot::White
+                    } else {
+                        Dot::Black
+                    };
+                    kropki.get_or_insert_default().add(dot, parse_pair(words)?);
+                }
+                "all-dots" => kropki = Some(kropki.unwrap_or_default().all_given()),
+                "x" | "v" => {
+                    let mark = if rule == "x" { XvMark::X } else { XvMark::V };
+                    xv.get_or_insert_default().add(mark, parse_pair(words)?);
+                }
+                "all-xv" => xv = Some(xv.unwrap_or_default().all_given()),
+                _ => return None,
+            }
+        }
+        if let Some(kropki) = kropki {
+            constraints.add(kropki);
+        }
+        if let Some(xv) = xv {
+            constraints.add(xv);
+        }
+        Some(Self {
+            sudoku: Sudoku::parse(&grid.join("\n"))?,
+            constraints,
+        })
+    }
+
+    /// The default configuration, with the rules of this puzzle
+    pub fn config(&self) -> SolverConfig {
+        SolverConfig {
+            constraints: self.constraints.clone(),
+            ..SolverConfig::default()
+        }
+    }
+
+    pub fn solve(&self) -> Option<SudokuSolution> {
+        self.sudoku.solve_with(&self.config())
+    }
+}
+
+/// Cells written as `r1c1` to `r9c9`
+fn parse_cells<'a>(words: impl Iterator<Item = &'a str>) -> Option<Vec<(usize, usize)>> {
+    words.map(parse_cell).collect()
+}
+
+/// Exactly two cells
+fn parse_pair<'a>(words: impl Iterator<Item = &'a str>) -> Option<Pair> {
+    match parse_cells(words)?[..] {
+        [a, b] => Some((a, b)),
+        _ => None,
+    }
+}
+
+fn parse_cell(word: &str) -> Option<(usize, usize)> {
+    let (row, col) = word.strip_prefix('r')?.split_once('c')?;
+    Some((parse_index(row)?, parse_index(col)?))
+}
+
+/// Row, column or box number from 1 to 9, counted from 0
+fn parse_index(number: &str) -> Option<usize> {
+    let number: usize = number.parse().ok()?;
+    (1..=9).contains(&number).then(|| number - 1)
+}
+
+impl From<Sudoku> for Puzzle {
+    /// A classic sudoku
+    fn from(sudoku: Sudoku) -> Self {
+        Self {
+            sudoku,
+            constraints: ConstraintSet::classic(),
+        }
+    }
+}
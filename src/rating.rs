@@ -0,0 +1,190 @@
+use std::fmt;
+
+use crate::{SolverConfig, Sudoku, SudokuPossibilities, SudokuSolution, strategy::Strategy};
+
+/// Difficulty added to the score when inference gets stuck and the
+/// puzzle can only be finished by guessing
+pub const GUESS_DIFFICULTY: u32 = 1000;
+
+/// How many times a strategy made progress
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrategyUse {
+    pub name: String,
+    pub difficulty: u32,
+    pub count: usize,
+}
+
+/// Strategies used while inferring a puzzle, and the score they add up to.
+///
+/// Each successful application of a strategy adds its
+/// [difficulty](Strategy::difficulty) to the score. Naked singles are part of
+/// basic propagation and aren't counted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rating {
+    uses: Vec<StrategyUse>,
+    /// Inference alone could not solve the puzzle
+    pub requires_guessing: bool,
+}
+
+impl Rating {
+    /// Count one successful application of `strategy`
+    pub fn record(&mut self, strategy: &dyn Strategy) {
+        if let Some(entry) = self.uses.iter_mut().find(|u| u.name == strategy.name()) {
+            entry.count += 1;
+        } else {
+            self.uses.push(StrategyUse {
+                name: strategy.name().to_owned(),
+                difficulty: strategy.difficulty(),
+                count: 1,
+            });
+        }
+    }
+
+    /// Strategies that made progress, in order of first use
+    pub fn uses(&self) -> &[StrategyUse] {
+        &self.uses
+    }
+
+    /// Number of times the named strategy made progress
+    pub fn count(&self, name: &str) -> usize {
+        self.uses
+            .iter()
+            .find(|u| u.name == name)
+            .map_or(0, |u| u.count)
+    }
+
+    /// The most difficult strategy used
+    pub fn hardest(&self) -> Option<&StrategyUse> {
+        self.uses.iter().max_by_key(|u| u.difficulty)
+    }
+
+    /// Sum of the difficulties of every strategy application
+    pub fn score(&self) -> u32 {
+        let guessing = if self.requires_guessing {
+            GUESS_DIFFICULTY
+        } else {
+            0
+        };
+        self.uses
+            .iter()
+            .map(|u| u.difficulty * u.count as u32)
+            .sum::<u32>()
+            + guessing
+    }
+}
+
+impl fmt::Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for u in &self.uses {
+            writeln!(f, "{:>4} x {} ({})", u.count, u.name, u.difficulty)?;
+        }
+        if self.requires_guessing {
+            writeln!(f, "   + guessing ({GUESS_DIFFICULTY})")?;
+        }
+        writeln!(f, "score {}", self.score())
+    }
+}
+
+impl Sudoku {
+    /// Rate the difficulty of the puzzle by solving it with inference only.
+    /// Returns `None` if the puzzle is found to be invalid.
+    pub fn rate(&self, config: &SolverConfig) -> Option<Rating> {
+        let mut sp = SudokuPossibilities::from(*self);
+        let mut rating = Rating::default();
+        sp.infer_rated(config, &mut rating).ok()?;
+        rating.requires_guessing = sp.solution(config).is_none();
+        Some(rating)
+    }
+}
+
+/// A solution together with the strategies used to reach it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveReport {
+    pub solution: SudokuSolution,
+    /// Strategies applied before the puzzle was solved or guessing was needed
+    pub rating: Rating,
+}
+
+impl SolveReport {
+    /// Strategies that made progress, with their counts
+    pub fn techniques(&self) -> &[StrategyUse] {
+        self.rating.uses()
+    }
+
+    /// The most difficult strategy used
+    pub fn hardest(&self) -> Option<&StrategyUse> {
+        self.rating.hardest()
+    }
+}
+
+impl SudokuPossibilities {
+    /// Solve, reporting which strategies were needed. If inference gets
+    /// stuck the rest is found by search, and the rating is marked as
+    /// requiring guessing.
+    pub fn solve_with_report(mut self, config: &SolverConfig) -> Option<SolveReport> {
+        let mut rating = Rating::default();
+        self.infer_rated(config, &mut rating).ok()?;
+        let solution = match self.solution(config) {
+            Some(solution) => solution,
+            None => {
+                rating.requires_guessing = true;
+                self.solve(config).ok()?
+            }
+        };
+        Some(SolveReport { solution, rating })
+    }
+}
+
+impl Sudoku {
+    pub fn solve_with_report(&self, config: &SolverConfig) -> Option<SolveReport> {
+        SudokuPossibilities::from(*self).solve_with_report(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::Technique;
+
+    #[test]
+    fn score_and_hardest() {
+        let mut rating = Rating::default();
+        rating.record(&Technique::HiddenSingles);
+        rating.record(&Technique::XWing);
+        rating.record(&Technique::HiddenSingles);
+        assert_eq!(rating.count("Hidden Single"), 2);
+        assert_eq!(rating.count("Swordfish"), 0);
+        assert_eq!(rating.hardest().unwrap().name, "X-Wing");
+        assert_eq!(
+            rating.score(),
+            2 * Technique::HiddenSingles.difficulty() + Technique::XWing.difficulty()
+        );
+    }
+
+    #[test]
+    fn guessing_rates_higher() {
+        let data = std::fs::read_to_string("puzzles/7gJb9G8fRt.txt").expect("Missing puzzle");
+        let sudoku = Sudoku::parse(&data).unwrap();
+        let full = sudoku.rate(&SolverConfig::default()).unwrap();
+        let basic = sudoku.rate(&SolverConfig::basic()).unwrap();
+        assert!(!full.requires_guessing);
+        assert!(basic.requires_guessing);
+        assert!(full.count("Hidden Single") > 0);
+        assert!(basic.score() > full.score());
+    }
+
+    #[test]
+    fn report_hardest_technique() {
+        let data = std::fs::read_to_string("puzzles/7gJb9G8fRt.txt").expect("Missing puzzle");
+        let sudoku = Sudoku::parse(&data).unwrap();
+        let report = sudoku.solve_with_report(&SolverConfig::default()).unwrap();
+        assert_eq!(Some(report.solution), sudoku.solve());
+        assert_eq!(report.hardest().unwrap().name, "Forcing Chain");
+        assert!(!report.rating.requires_guessing);
+
+        let report = sudoku.solve_with_report(&SolverConfig::basic()).unwrap();
+        assert_eq!(Some(report.solution), sudoku.solve());
+        assert!(report.rating.requires_guessing);
+        assert!(report.techniques().iter().all(|u| u.difficulty <= 17));
+    }
+}
@@ -0,0 +1,99 @@
+//! SAT backend, encoding the puzzle as CNF for [varisat](https://docs.rs/varisat).
+//!
+//! Variable `(r * 9 + c) * 9 + d` is true when digit `d` goes in cell `(r, c)`.
+
+use varisat::{ExtendFormula, Lit, Solver, Var};
+
+use crate::{Digit, Grid, Sudoku, SudokuSolution, unit::Unit};
+
+fn var((r, c): (usize, usize), d: usize) -> Var {
+    Var::from_index((r * 9 + c) * 9 + d)
+}
+
+/// Exactly one of `vars` is true
+fn exactly_one(solver: &mut Solver, vars: &[Var]) {
+    let any: Vec<Lit> = vars.iter().map(|v| v.positive()).collect();
+    solver.add_clause(&any);
+    for (i, a) in vars.iter().enumerate() {
+        for b in &vars[i + 1..] {
+            solver.add_clause(&[a.negative(), b.negative()]);
+        }
+    }
+}
+
+fn encode(sudoku: &Sudoku) -> Solver<'static> {
+    let mut solver = Solver::new();
+    for r in 0..9 {
+        for c in 0..9 {
+            let digits: Vec<Var> = (0..9).map(|d| var((r, c), d)).collect();
+            exactly_one(&mut solver, &digits);
+            if let Some(digit) = sudoku.grid[r][c] {
+                solver.add_clause(&[var((r, c), digit.index() as usize).positive()]);
+            }
+        }
+    }
+    for unit in Unit::ALL {
+        for d in 0..9 {
+            let places: Vec<Var> = unit.cells().into_iter().map(|cell| var(cell, d)).collect();
+            exactly_one(&mut solver, &places);
+        }
+    }
+    solver
+}
+
+/// Call `found` with each solution of the puzzle, until it returns false.
+/// Every solution is excluded by a new clause before searching for the next.
+pub fn solutions_sat(sudoku: &Sudoku, mut found: impl FnMut(SudokuSolution) -> bool) {
+    let mut solver = encode(sudoku);
+    while solver.solve().expect("SAT solver failed") {
+        let model = solver.model().expect("Satisfiable formula without a model");
+        let mut grid = Grid::splat(Digit::MIN);
+        let mut chosen = Vec::with_capacity(81);
+        for lit in model.into_iter().filter(|lit| lit.is_positive()) {
+            let (cell, d) = (lit.var().index() / 9, lit.var().index() % 9);
+            grid.grid[cell / 9][cell % 9] = Digit::from_index(d as u8).unwrap();
+            chosen.push(!lit);
+        }
+        let solution = SudokuSolution::new(grid).expect("Model is a valid solution");
+        if !found(solution) {
+            return;
+        }
+        solver.add_clause(&chosen);
+    }
+}
+
+/// First solution found by the SAT solver
+pub fn solve_sat(sudoku: &Sudoku) -> Option<SudokuSolution> {
+    let mut result = None;
+    solutions_sat(sudoku, |solution| {
+        result = Some(solution);
+        false
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_up_to_limit() {
+        let mut sudoku = Sudoku::splat(None);
+        sudoku.grid[0][0] = Some(Digit::unchecked(3));
+        let mut count = 0;
+        solutions_sat(&sudoku, |solution| {
+            assert_eq!(solution.get((0, 0)), Digit::unchecked(3));
+            count += 1;
+            count < 3
+        });
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn conflicting_givens() {
+        let mut sudoku = Sudoku::splat(None);
+        sudoku.grid[2][0] = Some(Digit::unchecked(6));
+        sudoku.grid[2][8] = Some(Digit::unchecked(6));
+        assert_eq!(solve_sat(&sudoku), None);
+    }
+}
@@ -0,0 +1,564 @@
+use std::{
+    cell::RefCell,
+    fmt::Write,
+    iter::FusedIterator,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    Digit, PossibleValues, SolveError, SolveStats, SolverConfig, Sudoku, SudokuPossibilities,
+    SudokuSolution,
+};
+
+/// Most stacks kept for reuse on each thread
+const MAX_SPARE_STACKS: usize = 8;
+
+thread_local! {
+    /// Emptied stacks of dropped searches. Solving many puzzles on a thread
+    /// reuses them instead of allocating new ones for every search.
+    static SPARE_STACKS: RefCell<Vec<(Vec<SudokuPossibilities>, Vec<usize>)>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// Outcome of a single [`Search::step`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// The state was a solution
+    Solved(SudokuSolution),
+    /// The state was broken or was split into guesses
+    Continue,
+    /// The solve was cancelled or ran out of time while inferring the
+    /// state, which is pending again
+    Stopped,
+    /// Nothing is left to search
+    Exhausted,
+}
+
+/// Depth-first search over guesses, keeping the pending states on an
+/// explicit stack instead of recursing. The search can be stopped after any
+/// step and continued later, and it finds every solution in turn.
+///
+/// Every pending guess is a full copy of the grid rather than a trail of
+/// changes to undo. A grid is a plain 162 byte value, and the `backtrack`
+/// benchmark finds restoring a copy faster than undoing the trail of a
+/// guess that changed 49 cells. Strategies also don't have to record their
+/// writes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Search {
+    stack: Vec<SudokuPossibilities>,
+    /// Number of guesses in effect for each pending state
+    depths: Vec<usize>,
+    nodes: usize,
+    stats: SolveStats,
+}
+
+impl Search {
+    pub fn new(sp: SudokuPossibilities) -> Self {
+        Self::at_depth(sp, 0)
+    }
+
+    /// Search a state that is already the result of `depth` guesses
+    pub(crate) fn at_depth(sp: SudokuPossibilities, depth: usize) -> Self {
+        let (mut stack, mut depths) = SPARE_STACKS
+            .with_borrow_mut(|spare| spare.pop())
+            .unwrap_or_default();
+        stack.push(sp);
+        depths.push(depth);
+        Self {
+            stack,
+            depths,
+            nodes: 0,
+            stats: SolveStats {
+                max_depth: depth,
+                ..SolveStats::default()
+            },
+        }
+    }
+
+    /// Number of states inferred so far
+    pub fn nodes(&self) -> usize {
+        self.nodes
+    }
+
+    /// Work done so far. The elapsed time only includes
+    /// [`next_solution`](Self::next_solution) calls.
+    pub fn stats(&self) -> SolveStats {
+        self.stats
+    }
+
+    /// States waiting to be searched, the next one last
+    pub fn pending(&self) -> &[SudokuPossibilities] {
+        &self.stack
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Infer the next pending state, and either report it as solved or
+    /// replace it with one guess for each candidate of its branch cell
+    pub fn step(&mut self, config: &SolverConfig) -> Step {
+        let (Some(mut sp), Some(depth)) = (self.stack.pop(), self.depths.pop()) else {
+            return Step::Exhausted;
+        };
+        self.nodes += 1;
+        self.stats.max_depth = self.stats.max_depth.max(depth);
+        let rounds = &mut self.stats.propagation_rounds;
+        match sp.infer_counting(config, None, rounds, |sp| config.halt(sp)) {
+            Ok(()) => {}
+            Err(SolveError::Unsolvable { .. }) => {
+                self.stats.backtracks += 1;
+                return Step::Continue;
+            }
+            // Whatever inference did so far still holds
+            Err(_) => {
+                self.stack.push(sp);
+                self.depths.push(depth);
+                return Step::Stopped;
+            }
+        }
+        if let Some(solution) = sp.solution(config) {
+            return Step::Solved(solution);
+        }
+        let Some(((r, c), values)) = sp.guess(config) else {
+            self.stats.backtracks += 1;
+            return Step::Continue;
+        };
+        // Pushed in reverse so that the first value is searched first
+        for &value in values.iter().rev() {
+            let mut guess = sp;
+            guess.grid[r][c] = PossibleValues::from(value);
+            self.stack.push(guess);
+            self.depths.push(depth + 1);
+            self.stats.guesses += 1;
+        }
+        Step::Continue
+    }
+
+    /// Search until the next solution is found, or until the search is
+    /// cancelled, runs out of time or exceeds its node budget or depth
+    /// limit. The error then holds the next pending state, and the search
+    /// can still be resumed.
+    pub fn next_solution(
+        &mut self,
+        config: &SolverConfig,
+    ) -> Result<Option<SudokuSolution>, SolveError> {
+        let nodes = AtomicUsize::new(self.nodes);
+        self.next_solution_until(config, &|| false, &nodes)
+    }
+
+    /// Like [`next_solution`](Self::next_solution), but gives up without a
+    /// result once `abandoned` returns true, as when another branch has
+    /// already found a solution. The node budget applies to `nodes`, which
+    /// counts the states inferred by every search of the same solve.
+    pub(crate) fn next_solution_until(
+        &mut self,
+        config: &SolverConfig,
+        abandoned: &dyn Fn() -> bool,
+        nodes: &AtomicUsize,
+    ) -> Result<Option<SudokuSolution>, SolveError> {
+        let start = Instant::now();
+        let result = self.search(config, abandoned, nodes);
+        self.stats.elapsed += start.elapsed();
+        result
+    }
+
+    fn search(
+        &mut self,
+        config: &SolverConfig,
+        abandoned: &dyn Fn() -> bool,
+        nodes: &AtomicUsize,
+    ) -> Result<Option<SudokuSolution>, SolveError> {
+        loop {
+            if abandoned() {
+                return Ok(None);
+            }
+            if let (Some(next), Some(&depth)) = (self.stack.last(), self.depths.last()) {
+                if let Some(error) = config.interruption(next, nodes.load(Ordering::Relaxed)) {
+                    return Err(error);
+                }
+                if config.max_depth.is_some_and(|max| depth > max) {
+                    return Err(SolveError::DepthLimit(Box::new(*next)));
+                }
+            }
+            let before = self.nodes;
+            let step = self.step(config);
+            nodes.fetch_add(self.nodes - before, Ordering::Relaxed);
+            match step {
+                Step::Solved(solution) => return Ok(Some(solution)),
+                // A stopped step is reported by the check at the top
+                Step::Continue | Step::Stopped => {}
+                Step::Exhausted => return Ok(None),
+            }
+        }
+    }
+}
+
+impl Drop for Search {
+    fn drop(&mut self) {
+        let mut stack = std::mem::take(&mut self.stack);
+        let mut depths = std::mem::take(&mut self.depths);
+        if stack.capacity() == 0 {
+            return;
+        }
+        stack.clear();
+        depths.clear();
+        // The spare stacks may already be gone if the thread is exiting
+        let _ = SPARE_STACKS.try_with(|spare| {
+            let mut spare = spare.borrow_mut();
+            if spare.len() < MAX_SPARE_STACKS {
+                spare.push((stack, depths));
+            }
+        });
+    }
+}
+
+impl Search {
+    /// Save the search as text, so that it can be continued later or in
+    /// another process with [`restore`](Self::restore). The config isn't
+    /// included and should be the same when resuming.
+    pub fn checkpoint(&self) -> String {
+        let mut out = String::new();
+        let stats = &self.stats;
+        writeln!(out, "nodes {}", self.nodes).unwrap();
+        writeln!(out, "guesses {}", stats.guesses).unwrap();
+        writeln!(out, "backtracks {}", stats.backtracks).unwrap();
+        writeln!(out, "propagation_rounds {}", stats.propagation_rounds).unwrap();
+        writeln!(out, "max_depth {}", stats.max_depth).unwrap();
+        writeln!(out, "elapsed_ns {}", stats.elapsed.as_nanos()).unwrap();
+        // Bottom of the stack first; each cell lists its candidates
+        for (sp, depth) in self.stack.iter().zip(&self.depths) {
+            write!(out, "state {depth}").unwrap();
+            for cell in sp.iter() {
+                out.push(' ');
+                if cell.is_broken() {
+                    out.push('-');
+                }
+                for digit in cell.iter() {
+                    write!(out, "{digit}").unwrap();
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Read a search saved by [`checkpoint`](Self::checkpoint)
+    pub fn restore(data: &str) -> Option<Self> {
+        let mut search = Self {
+            stack: Vec::new(),
+            depths: Vec::new(),
+            nodes: 0,
+            stats: SolveStats::default(),
+        };
+        for line in data.lines() {
+            let mut words = line.split_whitespace();
+            let key = words.next()?;
+            let value: usize = words.next()?.parse().ok()?;
+            match key {
+                "nodes" => search.nodes = value,
+                "guesses" => search.stats.guesses = value,
+                "backtracks" => search.stats.backtracks = value,
+                "propagation_rounds" => search.stats.propagation_rounds = value,
+                "max_depth" => search.stats.max_depth = value,
+                "elapsed_ns" => search.stats.elapsed = Duration::from_nanos(value as u64),
+                "state" => {
+                    let mut sp = SudokuPossibilities::EMPTY;
+                    for i in 0..81 {
+                        let mut cell = PossibleValues::EMPTY;
+                        for ch in words.next()?.chars().filter(|&ch| ch != '-') {
+                            cell.add(Digit::new(ch.to_digit(10)? as u8)?);
+                        }
+                        sp.grid[i / 9][i % 9] = cell;
+                    }
+                    search.stack.push(sp);
+                    search.depths.push(value);
+                }
+                _ => return None,
+            }
+            if words.next().is_some() {
+                return None;
+            }
+        }
+        Some(search)
+    }
+}
+
+/// Lazy iterator over every solution of a puzzle, see [`Sudoku::solutions`].
+/// If the search is cancelled, runs out of time or exceeds its node budget
+/// or depth limit, the error is yielded and iteration ends, so a stopped
+/// enumeration can be told apart from a finished one.
+#[derive(Debug, Clone)]
+pub struct Solutions {
+    search: Search,
+    config: SolverConfig,
+    stopped: bool,
+}
+
+impl Iterator for Solutions {
+    type Item = Result<SudokuSolution, SolveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.stopped {
+            return None;
+        }
+        let next = self.search.next_solution(&self.config).transpose();
+        self.stopped = !matches!(next, Some(Ok(_)));
+        next
+    }
+}
+
+impl FusedIterator for Solutions {}
+
+impl Sudoku {
+    /// Iterate over all solutions, finding each only when it's needed
+    pub fn solutions(&self) -> Solutions {
+        self.solutions_with(&SolverConfig::default())
+    }
+
+    /// Iterate over all solutions using the strategies of `config`.
+    /// Strategies that assume a unique solution are never used.
+    pub fn solutions_with(&self, config: &SolverConfig) -> Solutions {
+        Solutions {
+            search: Search::new(SudokuPossibilities::from(*self)),
+            config: SolverConfig {
+                assume_unique_solution: false,
+                ..config.clone()
+            },
+            stopped: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::{CancellationToken, Digit, Sudoku};
+
+    #[test]
+    fn pause_and_resume() {
+        let data = std::fs::read_to_string("puzzles/7gJb9G8fRt.txt").expect("Missing puzzle");
+        let sudoku = Sudoku::parse(&data).unwrap();
+        let config = SolverConfig::basic();
+
+        let mut search = Search::new(SudokuPossibilities::from(sudoku));
+        assert_eq!(search.step(&config), Step::Continue);
+        assert!(search.pending().len() > 1);
+        let mut resumed = search.clone();
+        assert_eq!(resumed.next_solution(&config), Ok(sudoku.solve()));
+        assert_eq!(resumed.next_solution(&config), Ok(None));
+        assert!(resumed.is_exhausted());
+        assert_eq!(search.next_solution(&config), Ok(sudoku.solve()));
+    }
+
+    #[test]
+    fn several_solutions() {
+        let config = SolverConfig::basic();
+        let mut search = Search::new(SudokuPossibilities::EMPTY);
+        let first = search.next_solution(&config).unwrap().unwrap();
+        let second = search.next_solution(&config).unwrap().unwrap();
+        assert_ne!(first, second);
+        assert_eq!(first.get((0, 0)), Digit::unchecked(1));
+        assert_eq!(second.get((0, 0)), Digit::unchecked(1));
+    }
+
+    #[test]
+    fn timeout_keeps_state() {
+        let config = SolverConfig {
+            deadline: Some(Instant::now()),
+            ..SolverConfig::basic()
+        };
+        let mut search = Search::new(SudokuPossibilities::EMPTY);
+        assert_eq!(
+            search.next_solution(&config),
+            Err(SolveError::Timeout(Box::new(SudokuPossibilities::EMPTY)))
+        );
+        assert!(!search.is_exhausted());
+        assert!(matches!(
+            Sudoku::splat(None).try_solve_with(&config),
+            Err(SolveError::Timeout(_))
+        ));
+    }
+
+    #[test]
+    fn cancelled_before_start() {
+        let token = CancellationToken::new();
+        let config = SolverConfig {
+            cancellation: Some(token.clone()),
+            ..SolverConfig::basic()
+        };
+        let mut search = Search::new(SudokuPossibilities::EMPTY);
+        assert!(search.next_solution(&config).unwrap().is_some());
+        token.cancel();
+        assert!(matches!(
+            search.next_solution(&config),
+            Err(SolveError::Cancelled(_))
+        ));
+        assert!(matches!(
+            Sudoku::splat(None).try_solve_with(&config),
+            Err(SolveError::Cancelled(_))
+        ));
+    }
+
+    #[test]
+    fn node_budget() {
+        let data = std::fs::read_to_string("puzzles/7gJb9G8fRt.txt").expect("Missing puzzle");
+        let sudoku = Sudoku::parse(&data).unwrap();
+        let mut config = SolverConfig::basic();
+        let mut search = Search::new(SudokuPossibilities::from(sudoku));
+        search.next_solution(&config).unwrap();
+        let needed = search.nodes();
+        assert!(needed > 1);
+
+        config.max_nodes = Some(needed - 1);
+        let mut search = Search::new(SudokuPossibilities::from(sudoku));
+        assert!(matches!(
+            search.next_solution(&config),
+            Err(SolveError::BudgetExceeded(_))
+        ));
+        config.max_nodes = Some(needed);
+        assert_eq!(search.next_solution(&config), Ok(sudoku.solve()));
+    }
+
+    #[test]
+    fn node_budget_is_shared() {
+        let data = std::fs::read_to_string("puzzles/7gJb9G8fRt.txt").expect("Missing puzzle");
+        let sudoku = Sudoku::parse(&data).unwrap();
+        let mut search = Search::new(SudokuPossibilities::from(sudoku));
+        search.next_solution(&SolverConfig::basic()).unwrap();
+        // Branches before the one with the smallest solution are searched to
+        // the end, so splitting the search can't make it need fewer nodes
+        // than the root it doesn't count
+        let needed = search.nodes() - 1;
+        for parallel_depth in 0..3 {
+            let mut config = SolverConfig {
+                parallel_depth,
+                max_nodes: Some(needed - 1),
+                ..SolverConfig::basic()
+            };
+            assert!(
+                matches!(
+                    sudoku.try_solve_with(&config),
+                    Err(SolveError::BudgetExceeded(_))
+                ),
+                "{parallel_depth}"
+            );
+            config.max_nodes = Some(needed * 2);
+            assert_eq!(sudoku.try_solve_with(&config).ok(), sudoku.solve());
+        }
+    }
+
+    #[test]
+    fn depth_limit() {
+        let data = std::fs::read_to_string("puzzles/7gJb9G8fRt.txt").expect("Missing puzzle");
+        let sudoku = Sudoku::parse(&data).unwrap();
+        let config = SolverConfig {
+            max_depth: Some(0),
+            ..SolverConfig::basic()
+        };
+        assert!(matches!(
+            sudoku.try_solve_with(&config),
+            Err(SolveError::DepthLimit(_))
+        ));
+        assert_eq!(
+            sudoku.try_solve_with(&SolverConfig::default()),
+            Ok(sudoku.solve().unwrap())
+        );
+    }
+
+    #[test]
+    fn raise_depth_limit_without_restarting() {
+        let data = std::fs::read_to_string("puzzles/7gJb9G8fRt.txt").expect("Missing puzzle");
+        let sudoku = Sudoku::parse(&data).unwrap();
+        let mut config = SolverConfig {
+            max_depth: Some(0),
+            ..SolverConfig::basic()
+        };
+        let mut search = Search::new(SudokuPossibilities::from(sudoku));
+        let mut limits = 0;
+        let solution = loop {
+            match search.next_solution(&config) {
+                Err(SolveError::DepthLimit(_)) => {
+                    limits += 1;
+                    config.max_depth = config.max_depth.map(|max| max + 1);
+                }
+                result => break result.unwrap(),
+            }
+        };
+        assert!(limits > 0);
+        assert_eq!(solution, sudoku.solve());
+
+        // Every state was inferred once, as in a search without a limit
+        let mut unlimited = Search::new(SudokuPossibilities::from(sudoku));
+        unlimited.next_solution(&SolverConfig::basic()).unwrap();
+        assert_eq!(search.nodes(), unlimited.nodes());
+    }
+
+    #[test]
+    fn checkpoint_and_restore() {
+        let data = std::fs::read_to_string("puzzles/7gJb9G8fRt.txt").expect("Missing puzzle");
+        let sudoku = Sudoku::parse(&data).unwrap();
+        let config = SolverConfig::basic();
+        let mut search = Search::new(SudokuPossibilities::from(sudoku));
+        search.step(&config);
+        search.step(&config);
+
+        let mut restored = Search::restore(&search.checkpoint()).unwrap();
+        assert_eq!(restored, search);
+        assert_eq!(restored.next_solution(&config), Ok(sudoku.solve()));
+        assert_eq!(Search::restore("state 0 12"), None);
+        assert_eq!(Search::restore("unknown 1"), None);
+    }
+
+    #[test]
+    fn states_are_small() {
+        assert_eq!(size_of::<SudokuPossibilities>(), 162);
+    }
+
+    #[test]
+    fn stacks_are_reused() {
+        let mut search = Search::new(SudokuPossibilities::EMPTY);
+        search.step(&SolverConfig::basic());
+        assert_eq!(search.pending().len(), 9);
+        drop(search);
+        let search = Search::new(SudokuPossibilities::EMPTY);
+        assert_eq!(search.pending(), [SudokuPossibilities::EMPTY]);
+        assert!(search.stack.capacity() >= 9);
+    }
+
+    #[test]
+    fn solutions_iterator() {
+        let mut sudoku = Sudoku::splat(None);
+        sudoku.grid[4][4] = Some(Digit::unchecked(9));
+        let config = SolverConfig::basic();
+        let first: Vec<_> = sudoku
+            .solutions_with(&config)
+            .take(3)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(first.len(), 3);
+        assert!(first.iter().all(|s| s.get((4, 4)) == Digit::unchecked(9)));
+        assert_ne!(first[0], first[1]);
+        assert_eq!(Some(first[0]), sudoku.solve_with(&config));
+
+        sudoku.grid[4][5] = Some(Digit::unchecked(9));
+        assert_eq!(sudoku.solutions().next(), None);
+    }
+
+    #[test]
+    fn solutions_report_interruption() {
+        let config = SolverConfig {
+            max_nodes: Some(5),
+            ..SolverConfig::basic()
+        };
+        let mut solutions = Sudoku::splat(None).solutions_with(&config);
+        assert!(matches!(
+            solutions.next(),
+            Some(Err(SolveError::BudgetExceeded(_)))
+        ));
+        assert_eq!(solutions.next(), None);
+    }
+}
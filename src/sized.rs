@@ -0,0 +1,318 @@
+use crate::{Digit, Grid, PossibleValues, Side, SideLength, solver::shuffle};
+
+/// A puzzle with `N` rows, columns and digits, with empty cells as `None`
+pub type SizedSudoku<const N: usize> = Grid<Option<Digit<N>>, N>;
+
+/// 16×16 sudoku with 4×4 boxes, its digits written from 1 to 9 and A to G
+pub type Hexadoku = SizedSudoku<16>;
+
+/// Candidates left for every cell of a [`SizedSudoku`]
+type State<const N: usize> = Grid<PossibleValues<N>, N>;
+
+/// A minimal solver for grids of `N` rows, columns and digits, with the
+/// rows, columns and boxes of its size. It fills in naked and hidden
+/// singles and guesses when they run out.
+///
+/// The rest of the crate is for 9×9 grids only: the strategies,
+/// constraints, engines, [`SolverConfig`](crate::SolverConfig),
+/// [`Search`](crate::Search) and the command line tool do not take other
+/// sizes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizedSolver<const N: usize> {
+    box_rows: usize,
+    box_cols: usize,
+    /// Cells of each row, column and box
+    units: Vec<[(usize, usize); N]>,
+    /// Cells sharing a unit with each cell, indexed by `row * N + col`
+    peers: Vec<Vec<(usize, usize)>>,
+}
+
+impl<const N: usize> SizedSolver<N>
+where
+    Side<N>: SideLength,
+{
+    /// Boxes as close to square as `N` allows, wider than they are tall:
+    /// 3×3 for 9, 2×3 for 6 and 3×4 for 12. Returns `None` if `N` has no
+    /// such boxes, as primes do.
+    pub fn new() -> Option<Self> {
+        let box_rows = (2..=N.isqrt()).rev().find(|&rows| N.is_multiple_of(rows))?;
+        Self::with_boxes(box_rows, N / box_rows)
+    }
+
+    /// Boxes of `box_rows` rows and `box_cols` columns, such as 4×3 boxes
+    /// for 12. Returns `None` unless they hold `N` cells.
+    pub fn with_boxes(box_rows: usize, box_cols: usize) -> Option<Self> {
+        if box_rows * box_cols != N {
+            return None;
+        }
+        let mut solver = Self {
+            box_rows,
+            box_cols,
+            units: Vec::with_capacity(3 * N),
+            peers: Vec::with_capacity(N * N),
+        };
+        let cells = || (0..N * N).map(|i| (i / N, i % N));
+        let units = (0..N)
+            .map(|r| std::array::from_fn(|c| (r, c)))
+            .chain((0..N).map(|c| std::array::from_fn(|r| (r, c))))
+            .chain((0..N).map(|b| {
+                let mut cells = cells().filter(|&cell| solver.box_of(cell) == b);
+                std::array::from_fn(|_| cells.next().expect("Boxes hold N cells"))
+            }))
+            .collect();
+        solver.units = units;
+        solver.peers = cells()
+            .map(|cell| {
+                let mut peers: Vec<_> = solver
+                    .units
+                    .iter()
+                    .filter(|unit| unit.contains(&cell))
+                    .flatten()
+                    .copied()
+                    .filter(|&peer| peer != cell)
+                    .collect();
+                peers.sort_unstable();
+                peers.dedup();
+                peers
+            })
+            .collect();
+        Some(solver)
+    }
+
+    /// Rows and columns of each box
+    pub fn box_shape(&self) -> (usize, usize) {
+        (self.box_rows, self.box_cols)
+    }
+
+    /// Box of a cell, numbered in reading order
+    pub fn box_of(&self, (r, c): (usize, usize)) -> usize {
+        r / self.box_rows * (N / self.box_cols) + c / self.box_cols
+    }
+
+    /// Parse `N` lines of `N` cells, as in [`Sudoku::parse`](crate::Sudoku::parse)
+    /// with digits above 9 written as letters. Returns `None` for other
+    /// characters.
+    pub fn parse(&self, data: &str) -> Option<SizedSudoku<N>> {
+        let mut sudoku = SizedSudoku::splat(None);
+        for (row, line) in sudoku.grid.iter_mut().zip(data.lines()) {
+            for (cell, c) in row.iter_mut().zip(line.chars()) {
+                if c != '.' && !c.is_whitespace() {
+                    *cell = Some(Digit::from_char(c)?);
+                }
+            }
+        }
+        Some(sudoku)
+    }
+
+    pub fn solve(&self, sudoku: &SizedSudoku<N>) -> Option<Grid<Digit<N>, N>> {
+        self.search(sudoku, 1, None).pop()
+    }
+
+    /// Number of solutions, counting no further than `limit`
+    pub fn count_solutions(&self, sudoku: &SizedSudoku<N>, limit: usize) -> usize {
+        self.search(sudoku, limit, None).len()
+    }
+
+    /// A puzzle with a unique solution, the same for the same seed. A random
+    /// grid is filled in, and then givens are taken out in random order as
+    /// long as the solution stays unique, so none of the remaining ones can
+    /// be taken out.
+    pub fn generate(&self, seed: u64) -> SizedSudoku<N> {
+        let filled = self
+            .search(&SizedSudoku::splat(None), 1, Some(seed))
+            .pop()
+            .expect("Empty grids have solutions");
+        let mut sudoku = filled.map(Some);
+        let mut cells: Vec<_> = (0..N * N).map(|i| (i / N, i % N)).collect();
+        shuffle(&mut cells, seed);
+        for (r, c) in cells {
+            let given = sudoku.grid[r][c].take();
+            if self.count_solutions(&sudoku, 2) > 1 {
+                sudoku.grid[r][c] = given;
+            }
+        }
+        sudoku
+    }
+
+    /// Whether `candidate` keeps every given and each unit of it holds
+    /// every digit once
+    pub fn is_solution(&self, sudoku: &SizedSudoku<N>, candidate: &Grid<Digit<N>, N>) -> bool {
+        let keeps_givens = sudoku
+            .iter()
+            .zip(candidate.iter())
+            .all(|(given, digit)| given.is_none_or(|given| given == *digit));
+        keeps_givens
+            && self.units.iter().all(|unit| {
+                let seen = unit.iter().fold(PossibleValues::EMPTY, |seen, &(r, c)| {
+                    seen | candidate.grid[r][c].into()
+                });
+                seen == PossibleValues::ANY
+            })
+    }
+
+    /// Fill in naked and hidden singles until there are none left,
+    /// returning `false` if a cell or a digit of a unit runs out of places
+    fn infer(&self, state: &mut State<N>) -> bool {
+        // Determined cells already removed from their peers
+        let mut placed = vec![false; N * N];
+        loop {
+            let mut progress = false;
+            for (i, peers) in self.peers.iter().enumerate() {
+                if placed[i] {
+                    continue;
+                }
+                let Some(digit) = state.grid[i / N][i % N].determined() else {
+                    continue;
+                };
+                placed[i] = true;
+                for &(r, c) in peers {
+                    if state.grid[r][c].contains(digit) {
+                        state.grid[r][c].remove(digit);
+                        if state.grid[r][c].is_broken() {
+                            return false;
+                        }
+                        progress = true;
+                    }
+                }
+            }
+            for unit in &self.units {
+                // Digits with a place in the unit, and with more than one
+                let (mut once, mut twice) = (PossibleValues::EMPTY, PossibleValues::EMPTY);
+                for &(r, c) in unit {
+                    twice |= once & state.grid[r][c];
+                    once |= state.grid[r][c];
+                }
+                if once != PossibleValues::ANY {
+                    return false;
+                }
+                let hidden = once & !twice;
+                for &(r, c) in unit {
+                    let single = state.grid[r][c] & hidden;
+                    if !single.is_broken() && state.grid[r][c].count() > 1 {
+                        // Two digits with no other place than the same cell
+                        if single.count() > 1 {
+                            return false;
+                        }
+                        state.grid[r][c] = single;
+                        progress = true;
+                    }
+                }
+            }
+            if !progress {
+                return true;
+            }
+        }
+    }
+
+    /// Depth-first search for up to `limit` solutions, guessing on the cell
+    /// with the fewest candidates. With a `seed` the candidates are tried in
+    /// random order, otherwise in ascending order. The states to search are
+    /// kept on the heap, as large grids can need hundreds of nested guesses.
+    fn search(
+        &self,
+        sudoku: &SizedSudoku<N>,
+        limit: usize,
+        seed: Option<u64>,
+    ) -> Vec<Grid<Digit<N>, N>> {
+        let mut found = Vec::new();
+        let mut stack = vec![sudoku.map(PossibleValues::initial_state)];
+        while let Some(mut state) = stack.pop() {
+            if found.len() >= limit {
+                break;
+            }
+            if !self.infer(&mut state) {
+                continue;
+            }
+            let guess = (0..N * N)
+                .map(|i| (i / N, i % N))
+                .filter(|&(r, c)| state.grid[r][c].count() > 1)
+                .min_by_key(|&(r, c)| state.grid[r][c].count());
+            let Some((r, c)) = guess else {
+                found.extend(state.try_map(|cell| cell.determined()));
+                continue;
+            };
+            let mut digits = state.grid[r][c].options();
+            if let Some(seed) = seed {
+                shuffle(&mut digits, seed ^ (r * N + c) as u64);
+            }
+            // Pushed in reverse, so that the first digit is tried first
+            for digit in digits.into_iter().rev() {
+                let mut branch = state;
+                branch.grid[r][c] = digit.into();
+                stack.push(branch);
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solves_empty<const N: usize>()
+    where
+        Side<N>: SideLength,
+    {
+        let solver = SizedSolver::<N>::new().unwrap();
+        let empty = SizedSudoku::splat(None);
+        let solution = solver.solve(&empty).unwrap();
+        assert!(solver.is_solution(&empty, &solution));
+    }
+
+    #[test]
+    fn square_sizes() {
+        solves_empty::<4>();
+        solves_empty::<9>();
+        solves_empty::<16>();
+        solves_empty::<25>();
+        assert!(SizedSolver::<7>::new().is_none());
+    }
+
+    #[test]
+    fn mini_sizes() {
+        solves_empty::<6>();
+        let solver = SizedSolver::<6>::new().unwrap();
+        // The second box is to the right of the first
+        assert!(solver.units[12..].contains(&[(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)]));
+        assert!(solver.units[12..].contains(&[(0, 3), (0, 4), (0, 5), (1, 3), (1, 4), (1, 5)]));
+
+        let data = std::fs::read_to_string("puzzles/sized/mini.txt").expect("Missing puzzle");
+        let sudoku = solver.parse(&data).unwrap();
+        assert_eq!(solver.count_solutions(&sudoku, 2), 1);
+
+        let generated = solver.generate(3);
+        assert_eq!(generated, solver.generate(3));
+        assert_eq!(solver.count_solutions(&generated, 2), 1);
+        let small = SizedSolver::<4>::new().unwrap();
+        assert_eq!(small.count_solutions(&small.generate(5), 2), 1);
+    }
+
+    #[test]
+    fn rectangular_boxes() {
+        let solver = SizedSolver::<12>::new().unwrap();
+        assert_eq!(solver.box_shape(), (3, 4));
+        assert_eq!(solver.box_of((2, 4)), 1);
+        assert_eq!(solver.box_of((3, 0)), 3);
+        let tall = SizedSolver::<12>::with_boxes(4, 3).unwrap();
+        assert_eq!(tall.box_of((3, 0)), 0);
+        assert_eq!(tall.box_of((4, 11)), 7);
+        assert!(SizedSolver::<12>::with_boxes(2, 5).is_none());
+
+        let data = std::fs::read_to_string("puzzles/sized/twelve.txt").expect("Missing puzzle");
+        let sudoku = solver.parse(&data).unwrap();
+        let solution = solver.solve(&sudoku).unwrap();
+        assert!(solver.is_solution(&sudoku, &solution));
+        assert_eq!(solver.count_solutions(&sudoku, 2), 1);
+    }
+
+    #[test]
+    fn solve_hexadoku() {
+        let data = std::fs::read_to_string("puzzles/sized/hexadoku.txt").expect("Missing puzzle");
+        let solver = SizedSolver::<16>::new().unwrap();
+        let sudoku: Hexadoku = solver.parse(&data).unwrap();
+        let solution = solver.solve(&sudoku).unwrap();
+        assert!(solver.is_solution(&sudoku, &solution));
+        assert_eq!(solver.count_solutions(&sudoku, 2), 1);
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+
+use crate::{ConstraintSet, Digit, Grid, PossibleValues, Sudoku, unit::Unit};
+
+/// A filled grid in which every row, column and box holds each digit once,
+/// or that follows the rules of a [`ConstraintSet`].
+/// Can only be constructed through [`new`](Self::new) or
+/// [`with_constraints`](Self::with_constraints), which check this.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SudokuSolution(Grid<Digit>);
+
+impl SudokuSolution {
+    /// Accept the grid if every unit holds each digit once
+    pub fn new(grid: Grid<Digit>) -> Option<Self> {
+        let complete = Unit::ALL.iter().all(|unit| {
+            let mut seen = PossibleValues::EMPTY;
+            for (r, c) in unit.cells() {
+                seen.add(grid.grid[r][c]);
+            }
+            seen == PossibleValues::ANY
+        });
+        complete.then_some(Self(grid))
+    }
+
+    /// Accept the grid if it follows every constraint of the set, which may
+    /// have replaced the boxes with other regions
+    pub fn with_constraints(grid: Grid<Digit>, constraints: &ConstraintSet) -> Option<Self> {
+        if constraints.regions().is_none() {
+            Self::new(grid).filter(|solution| constraints.is_satisfied(solution.grid()))
+        } else {
+            constraints.is_satisfied(&grid).then_some(Self(grid))
+        }
+    }
+
+    pub fn get(&self, (r, c): (usize, usize)) -> Digit {
+        self.0.grid[r][c]
+    }
+
+    pub fn grid(&self) -> &Grid<Digit> {
+        &self.0
+    }
+}
+
+impl From<SudokuSolution> for Grid<Digit> {
+    fn from(solution: SudokuSolution) -> Self {
+        solution.0
+    }
+}
+
+impl From<SudokuSolution> for Sudoku {
+    /// The solution as a puzzle with every cell given
+    fn from(solution: SudokuSolution) -> Self {
+        solution.0.map(Some)
+    }
+}
+
+impl fmt::Display for SudokuSolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_incomplete_units() {
+        let data = std::fs::read_to_string("puzzles/wiki.txt").expect("Missing puzzle");
+        let solution = Sudoku::parse(&data).unwrap().solve().unwrap();
+        assert_eq!(SudokuSolution::new(*solution.grid()), Some(solution));
+        assert_eq!(Sudoku::from(solution).solve(), Some(solution));
+
+        // Only the last column is wrong
+        let mut grid = Grid::from(solution);
+        grid.grid[0][8] = grid.grid[0][7];
+        assert_eq!(SudokuSolution::new(grid), None);
+        assert_eq!(SudokuSolution::new(Grid::splat(Digit::MIN)), None);
+    }
+}
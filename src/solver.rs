@@ -1,8 +1,22 @@
-use crate::{Grid, PossibleValues, Sudoku, SudokuSolution};
-use std::fmt;
-
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
-
+use crate::{
+    Bitboard, Conflict, ConstraintSet, Digit, Grid, PossibleValues, Rating, Search, SolveError,
+    SolveOutcome, SolveStats, SolverConfig, Sudoku, SudokuSolution, ValueOrder,
+    strategy::{Strategy, StrategyResult, Technique},
+    unit::{CLASSIC, Unit, Units, peers},
+};
+use std::{
+    cmp::Reverse,
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+
+/// Candidates left for every cell. This is the partial state of a solve:
+/// it can be inferred further, edited and solved again.
 pub type SudokuPossibilities = Grid<PossibleValues>;
 
 impl From<Sudoku> for SudokuPossibilities {
@@ -40,170 +54,570 @@ impl SudokuPossibilities {
         grid: [[PossibleValues::ANY; 9]; 9],
     };
 
+    /// Candidates left for a cell
+    pub fn candidates(&self, (r, c): (usize, usize)) -> PossibleValues {
+        self.grid[r][c]
+    }
+
+    /// Fill in a cell, as if it were a given
+    pub fn place(&mut self, (r, c): (usize, usize), digit: Digit) {
+        self.grid[r][c] = PossibleValues::from(digit);
+    }
+
+    /// The cells that have been solved so far
+    pub fn to_sudoku(&self) -> Sudoku {
+        self.map(|cell| cell.determined())
+    }
+
+    /// The solution, if every cell is determined. Broken states have none,
+    /// which [`SudokuSolution::new`] checks anyway.
     pub fn solved(&self) -> Option<SudokuSolution> {
-        assert!(!self.is_broken(), "Cannot operate on a broken sudoku");
         self.try_map(|cell| cell.determined())
+            .and_then(SudokuSolution::new)
+    }
+
+    /// The solution, if every cell is determined and the grid follows the
+    /// constraints of `config`
+    pub(crate) fn solution(&self, config: &SolverConfig) -> Option<SudokuSolution> {
+        self.try_map(|cell| cell.determined())
+            .and_then(|grid| SudokuSolution::with_constraints(grid, &config.constraints))
     }
 
     pub fn is_broken(&self) -> bool {
-        if self.iter().any(|cell| cell.is_broken()) {
-            return true;
-        }
-
-        for i in 0..9 {
-            for j in 0..8 {
-                for k in j + 1..9 {
-                    // row
-                    let a = self.grid[i][j].determined();
-                    let b = self.grid[i][k].determined();
-                    if a.is_some() && a == b {
-                        return true;
-                    }
+        self.conflict().is_some()
+    }
 
-                    // col
-                    let a = self.grid[j][i].determined();
-                    let b = self.grid[k][i].determined();
-                    if a.is_some() && a == b {
-                        return true;
-                    }
+    /// A cell without candidates, or two solved cells that share a unit
+    /// and hold the same digit
+    pub fn conflict(&self) -> Option<Conflict> {
+        self.conflict_in(&CLASSIC)
+    }
 
-                    // cell
-                    let ax = (i / 3) * 3 + (j / 3);
-                    let ay = (i % 3) * 3 + (j % 3);
+    /// Like [`conflict`](Self::conflict), with the regions of `constraints`
+    /// in place of the boxes if it has any
+    pub fn conflict_with(&self, constraints: &ConstraintSet) -> Option<Conflict> {
+        self.conflict_in(constraints.units())
+    }
 
-                    let bx = (i / 3) * 3 + (k / 3);
-                    let by = (i % 3) * 3 + (k % 3);
+    fn conflict_in(&self, units: &Units) -> Option<Conflict> {
+        if let Some(i) = self.cells().iter().position(|cell| cell.is_broken()) {
+            return Some(Conflict::Empty((i / 9, i % 9)));
+        }
 
-                    let a = self.grid[ax][ay].determined();
-                    let b = self.grid[bx][by].determined();
-                    if a.is_some() && a == b {
-                        return true;
-                    }
+        for unit in Unit::ALL {
+            let mut seen = [None; 9];
+            for (r, c) in units.cells(unit) {
+                let Some(digit) = self.grid[r][c].determined() else {
+                    continue;
+                };
+                if let Some(other) = seen[digit.index() as usize].replace((r, c)) {
+                    return Some(Conflict::Duplicate {
+                        cells: [other, (r, c)],
+                        digit,
+                    });
                 }
             }
         }
 
-        false
+        None
     }
 
-    /// Do a full round of inference
-    pub fn infer_step(&mut self) -> Result<(), Broken> {
-        if self.is_broken() {
-            return Err(Broken);
-        }
+    /// Remove the value of every determined cell from its peers, following up
+    /// on cells that become determined along the way. Digits placed in each
+    /// unit are tracked as they're found, so conflicts are caught without
+    /// rescanning the grid.
+    ///
+    /// This is scalar on purpose: `std::simd` is unstable and explicit
+    /// intrinsics need `unsafe`, which the crate forbids. A determined cell
+    /// only touches its 20 peers, which is too little work to batch.
+    pub fn propagate(&mut self) -> Result<(), Conflict> {
+        self.propagate_from(Bitboard::ALL, false, &CLASSIC)
+    }
 
-        for i in 0..9 {
-            for j in 0..9 {
-                if self.grid[i][j].determined().is_some() {
-                    continue;
+    /// Like [`propagate`](Self::propagate), but only starting from the
+    /// `dirty` cells. The other cells must have been propagated already, and
+    /// only lost candidates since, so their values are gone from their peers
+    /// and cannot clash with anything new.
+    ///
+    /// With `hidden_singles`, a digit is also placed as soon as it has only
+    /// one cell left in some unit. The units are those of `units`, so that
+    /// regions can take the place of the boxes.
+    fn propagate_from(
+        &mut self,
+        dirty: Bitboard,
+        hidden_singles: bool,
+        units: &Units,
+    ) -> Result<(), Conflict> {
+        let mut placed = [PossibleValues::EMPTY; 27];
+        let mut queue = Vec::with_capacity(81);
+        for (r, c) in dirty.cells() {
+            match self.grid[r][c].count() {
+                0 => return Err(Conflict::Empty((r, c))),
+                1 => {
+                    self.mark_placed(&mut placed, (r, c), units)?;
+                    queue.push((r, c));
                 }
-
-                for opt in self.grid[i][j].options() {
-                    let mut copy = *self;
-                    copy.grid[i][j] = PossibleValues::from(opt);
-                    if copy.is_broken() {
-                        self.grid[i][j].remove(opt);
+                _ => {}
+            }
+        }
+        let mut counts = hidden_singles.then(|| DigitCounts::new(self, units));
+        loop {
+            while let Some((r, c)) = queue.pop() {
+                let value = self.grid[r][c];
+                for &(pr, pc) in units.peers((r, c)) {
+                    let peer = &mut self.grid[pr][pc];
+                    if *peer & value == PossibleValues::EMPTY {
+                        continue;
+                    }
+                    *peer &= !value;
+                    if let Some(counts) = &mut counts {
+                        counts.remove(units, (pr, pc), value);
+                    }
+                    match peer.count() {
+                        0 => return Err(Conflict::Empty((pr, pc))),
+                        1 => {
+                            self.mark_placed(&mut placed, (pr, pc), units)?;
+                            queue.push((pr, pc));
+                        }
+                        _ => {}
                     }
                 }
             }
+            let Some(counts) = &mut counts else {
+                return Ok(());
+            };
+            let Some((unit, digit)) = counts.singles.pop() else {
+                return Ok(());
+            };
+            // The count may have dropped to 0 since, or the digit been placed
+            let Some((r, c)) = units
+                .cells(Unit::ALL[unit])
+                .into_iter()
+                .find(|&(r, c)| self.grid[r][c].contains(digit))
+            else {
+                continue;
+            };
+            let single = PossibleValues::from(digit);
+            if self.grid[r][c] != single {
+                counts.remove(units, (r, c), self.grid[r][c] & !single);
+                self.grid[r][c] = single;
+                self.mark_placed(&mut placed, (r, c), units)?;
+                queue.push((r, c));
+            }
         }
+    }
 
-        if self.is_broken() {
-            return Err(Broken);
+    /// Record the digit of a determined cell in the masks of its row, column
+    /// and region, failing if one of them already has it
+    fn mark_placed(
+        &self,
+        placed: &mut [PossibleValues; 27],
+        (r, c): (usize, usize),
+        units: &Units,
+    ) -> Result<(), Conflict> {
+        let value = self.grid[r][c];
+        for unit in units.of((r, c)) {
+            if placed[unit] & value != PossibleValues::EMPTY {
+                let digit = value.determined().expect("Cell is determined");
+                let other = units
+                    .cells(Unit::ALL[unit])
+                    .into_iter()
+                    .find(|&(or, oc)| (or, oc) != (r, c) && self.grid[or][oc] == value)
+                    .expect("Placed digit has a cell");
+                return Err(Conflict::Duplicate {
+                    cells: [other, (r, c)],
+                    digit,
+                });
+            }
+            placed[unit] |= value;
         }
+        Ok(())
+    }
 
+    /// Do a full round of inference
+    pub fn infer_step(&mut self, config: &SolverConfig) -> Result<(), Conflict> {
+        self.infer_step_rated(config, &mut Rating::default())
+    }
+
+    /// Do a full round of inference, recording the strategy that made progress
+    pub fn infer_step_rated(
+        &mut self,
+        config: &SolverConfig,
+        rating: &mut Rating,
+    ) -> Result<(), Conflict> {
+        self.propagate_from(Bitboard::ALL, false, config.constraints.units())?;
+        if config.constraints.propagate(self) || self.apply_strategy(config, rating) {
+            // Clashing digits placed by the strategy are caught by the next
+            // propagation, but emptied cells must be reported now
+            if let Some(i) = self.cells().iter().position(|cell| cell.is_broken()) {
+                return Err(Conflict::Empty((i / 9, i % 9)));
+            }
+        }
         Ok(())
     }
 
-    pub fn infer(&mut self) -> Result<(), Broken> {
+    /// Apply the first strategy that makes progress, returning whether any did
+    fn apply_strategy(&mut self, config: &SolverConfig, rating: &mut Rating) -> bool {
+        let units = config.constraints.units();
+        for strategy in config.enabled_strategies() {
+            if strategy.apply_in(self, units) == StrategyResult::Progress {
+                rating.record(strategy);
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn infer(&mut self, config: &SolverConfig) -> Result<(), Conflict> {
+        self.infer_counting(config, None, &mut 0, |_| None)
+    }
+
+    /// Infer until no more progress is made, recording the strategies used
+    pub fn infer_rated(
+        &mut self,
+        config: &SolverConfig,
+        rating: &mut Rating,
+    ) -> Result<(), Conflict> {
+        self.infer_counting(config, Some(rating), &mut 0, |_| None)
+    }
+
+    /// Infer until no more progress is made, adding the number of rounds
+    /// done to `rounds`, even if the state turns out to be broken.
+    ///
+    /// Each round only propagates from the cells the previous round's
+    /// strategy changed, so rounds on nearly solved grids stay cheap. Those
+    /// include any cell the strategy emptied, so conflicts are only ever
+    /// detected by propagation.
+    /// Without a `rating` to record them in, hidden singles are placed
+    /// during propagation instead of by their strategy, if it is enabled.
+    /// Before each round `halt` can stop inference with an error, such as
+    /// when the solve has run out of time.
+    pub(crate) fn infer_counting<E: From<Conflict>>(
+        &mut self,
+        config: &SolverConfig,
+        rating: Option<&mut Rating>,
+        rounds: &mut usize,
+        halt: impl Fn(&Self) -> Option<E>,
+    ) -> Result<(), E> {
+        let hidden_singles = rating.is_none()
+            && config
+                .strategies
+                .iter()
+                .any(|strategy| strategy.name() == Technique::HiddenSingles.name());
+        let units = config.constraints.units();
+        let mut unrated = Rating::default();
+        let rating = rating.unwrap_or(&mut unrated);
+        let mut dirty = Bitboard::ALL;
         loop {
-            let original = *self;
-            self.infer_step()?;
-            if *self == original {
+            if let Some(error) = halt(self) {
+                break Err(error);
+            }
+            *rounds += 1;
+            self.propagate_from(dirty, hidden_singles, units)?;
+            let propagated = *self;
+            if !(config.constraints.propagate(self) || self.apply_strategy(config, rating)) {
                 break Ok(());
             }
+            dirty = self.changed_since(&propagated);
         }
     }
 
-    pub fn recursive_hypothetical(
-        &mut self,
-        depth: usize,
-        limit: usize,
-    ) -> Result<SudokuSolution, CannotSolve> {
-        self.infer()?;
+    /// Next cell to branch on: the undetermined cell with the fewest
+    /// candidates, preferring cells with more unsolved peers on ties
+    pub(crate) fn branch_cell(&self) -> Option<(usize, usize)> {
+        (0..81)
+            .map(|i| (i / 9, i % 9))
+            .filter(|&(r, c)| self.grid[r][c].count() > 1)
+            .min_by_key(|&(r, c)| {
+                let unsolved = peers((r, c))
+                    .filter(|&(pr, pc)| self.grid[pr][pc].count() > 1)
+                    .count();
+                (self.grid[r][c].count(), Reverse(unsolved))
+            })
+    }
 
-        if let Some(solution) = self.solved() {
-            return Ok(solution);
-        } else if depth > limit {
-            return Err(CannotSolve::DepthLimit(*self));
+    /// Cell to guess on and its candidates in the order they should be
+    /// tried. In deterministic mode that is the first unsolved cell in
+    /// ascending order, so that the first solution found is the smallest.
+    pub(crate) fn guess(&self, config: &SolverConfig) -> Option<((usize, usize), GuessOrder)> {
+        let cell = if config.deterministic {
+            (0..81)
+                .map(|i| (i / 9, i % 9))
+                .find(|&(r, c)| self.grid[r][c].count() > 1)?
+        } else {
+            self.branch_cell()?
+        };
+        let order = if config.deterministic {
+            ValueOrder::Ascending
+        } else {
+            config.value_order
+        };
+        Some((cell, self.ordered_values(cell, order)))
+    }
+
+    /// Candidates of a cell in the order they should be tried
+    pub(crate) fn ordered_values(&self, (r, c): (usize, usize), order: ValueOrder) -> GuessOrder {
+        let mut values = GuessOrder::new(self.grid[r][c]);
+        match order {
+            ValueOrder::Ascending => {}
+            ValueOrder::LeastConstraining => values.sort_by_key(|&digit| {
+                peers((r, c))
+                    .filter(|&(pr, pc)| self.grid[pr][pc].contains(digit))
+                    .count()
+            }),
+            ValueOrder::Frequency => values.sort_by_key(|&digit| {
+                self.iter()
+                    .filter(|cell| cell.determined() == Some(digit))
+                    .count()
+            }),
+            // Seeded per cell so each branch point shuffles differently
+            ValueOrder::Random { seed } => shuffle(&mut values, seed ^ (r * 9 + c) as u64),
         }
+        values
+    }
 
-        for i in 0..9 {
-            for j in 0..9 {
-                if self.grid[i][j].determined().is_some() {
-                    continue;
-                }
+    /// Solve using inference only, never guessing
+    pub fn solve_logical(mut self, config: &SolverConfig) -> Result<LogicalOutcome, Conflict> {
+        self.infer(config)?;
+        Ok(match self.solution(config) {
+            Some(solution) => LogicalOutcome::Solved(solution),
+            None => LogicalOutcome::Stuck(self),
+        })
+    }
+
+    /// Solve with inference, guessing when it gets stuck. With the `parallel`
+    /// feature the branches of the top guesses are searched in parallel, and
+    /// the first solution in the order of the guesses is returned.
+    pub fn solve(self, config: &SolverConfig) -> Result<SudokuSolution, SolveError> {
+        self.solve_with_stats(config)
+            .map(|outcome| outcome.solution)
+    }
+
+    /// Like [`solve`](Self::solve), also counting the work done
+    pub fn solve_with_stats(mut self, config: &SolverConfig) -> Result<SolveOutcome, SolveError> {
+        let start = Instant::now();
+        let mut stats = SolveStats::default();
+        self.infer_counting(config, None, &mut stats.propagation_rounds, |sp| {
+            config.halt(sp)
+        })?;
+        // Shared by every branch, so that they all draw on one node budget
+        let nodes = AtomicUsize::new(0);
+        #[cfg(feature = "parallel")]
+        let solution = config
+            .parallelism
+            .install(|| self.search_guesses(config, 0, &mut stats, &|| false, &nodes));
+        #[cfg(not(feature = "parallel"))]
+        let solution = self.search_guesses(config, 0, &mut stats, &|| false, &nodes);
+        stats.elapsed = start.elapsed();
+        Ok(SolveOutcome {
+            solution: solution?.ok_or(SolveError::Unsolvable { conflict: None })?,
+            stats,
+        })
+    }
 
-                let mut alts = Vec::new();
-                for opt in self.grid[i][j]
-                    .options()
-                    .into_par_iter()
-                    .map(|opt| {
-                        let mut copy = *self;
-                        copy.grid[i][j] = PossibleValues::from(opt);
-                        copy.recursive_hypothetical(depth + 1, limit)
-                    })
-                    .collect::<Vec<_>>()
-                {
-                    match opt {
-                        Ok(solved) => return Ok(solved),
-                        Err(CannotSolve::Broken) => {}
-                        Err(CannotSolve::DepthLimit(alt)) => alts.push(alt),
+    /// Search the guesses of an inferred state, which is the result of
+    /// `depth` guesses. Branches above [`SolverConfig::parallel_depth`] are
+    /// split further, the rest are searched one at a time. Once a branch
+    /// finds a solution, the branches that can no longer matter stop early.
+    /// Every state inferred is counted in `nodes` for the node budget.
+    fn search_guesses(
+        self,
+        config: &SolverConfig,
+        depth: usize,
+        stats: &mut SolveStats,
+        abandoned: &(dyn Fn() -> bool + Sync),
+        nodes: &AtomicUsize,
+    ) -> Result<Option<SudokuSolution>, SolveError> {
+        if depth >= config.parallel_depth {
+            let mut search = Search::at_depth(self, depth);
+            let result = search.next_solution_until(config, abandoned, nodes);
+            *stats += search.stats();
+            return result;
+        }
+        if let Some(solution) = self.solution(config) {
+            return Ok(Some(solution));
+        }
+        let Some(((r, c), values)) = self.guess(config) else {
+            return Ok(None);
+        };
+        // Index of the first branch known to have a solution
+        let found = AtomicUsize::new(usize::MAX);
+        let search_branch = |(i, value)| {
+            let abandoned = || {
+                let first = found.load(Ordering::Relaxed);
+                // Earlier branches may still hold a smaller solution
+                abandoned()
+                    || if config.deterministic {
+                        first < i
+                    } else {
+                        first != usize::MAX
                     }
+            };
+            let mut guess = self;
+            guess.grid[r][c] = PossibleValues::from(value);
+            let mut stats = SolveStats {
+                max_depth: depth + 1,
+                ..SolveStats::default()
+            };
+            let result = if depth + 1 >= config.parallel_depth {
+                let mut search = Search::at_depth(guess, depth + 1);
+                let result = search.next_solution_until(config, &abandoned, nodes);
+                stats = search.stats();
+                result
+            } else if let Some(error) =
+                config.interruption(&guess, nodes.fetch_add(1, Ordering::Relaxed))
+            {
+                Err(error)
+            } else {
+                let rounds = &mut stats.propagation_rounds;
+                match guess.infer_counting(config, None, rounds, |sp| config.halt(sp)) {
+                    Ok(()) => {
+                        guess.search_guesses(config, depth + 1, &mut stats, &abandoned, nodes)
+                    }
+                    Err(SolveError::Unsolvable { .. }) => {
+                        stats.backtracks += 1;
+                        Ok(None)
+                    }
+                    Err(error) => Err(error),
                 }
-                let mut combined = alts.pop().unwrap_or(Grid::splat(PossibleValues::EMPTY));
-                while let Some(a) = alts.pop() {
-                    combined |= a;
-                }
-                self.grid = combined.grid;
+            };
+            if let Ok(Some(_)) = result {
+                found.fetch_min(i, Ordering::Relaxed);
+            }
+            (result, stats)
+        };
+        #[cfg(feature = "parallel")]
+        let results: Vec<_> = values
+            .par_iter()
+            .copied()
+            .enumerate()
+            .map(search_branch)
+            .collect();
+        // Searched lazily, so branches after the first solution are skipped
+        #[cfg(not(feature = "parallel"))]
+        let results = values.iter().copied().enumerate().map(search_branch);
+
+        let mut interrupted = None;
+        for (result, branch) in results {
+            *stats += branch;
+            stats.guesses += 1;
+            match result {
+                Ok(Some(solution)) => return Ok(Some(solution)),
+                Ok(None) => {}
+                // A later branch can't be returned without knowing whether
+                // this one held a smaller solution
+                Err(error) if config.deterministic => return Err(error),
+                Err(error) => interrupted = Some(error),
             }
         }
+        interrupted.map_or(Ok(None), Err)
+    }
+}
 
-        Err(CannotSolve::DepthLimit(*self))
+/// Shuffle reproducibly for the same seed, with xorshift64*
+pub(crate) fn shuffle<T>(values: &mut [T], seed: u64) {
+    let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    for i in (1..values.len()).rev() {
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        let j = (state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 32) as usize % (i + 1);
+        values.swap(i, j);
     }
+}
 
-    pub fn solve(mut self) -> Result<SudokuSolution, Broken> {
-        let mut limit = 1;
-        loop {
-            match self.recursive_hypothetical(1, limit) {
-                Ok(solved) => return Ok(solved),
-                Err(CannotSolve::Broken) => return Err(Broken),
-                Err(CannotSolve::DepthLimit(_)) => {
-                    limit += 1;
-                }
-            }
+/// Result of solving without guessing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogicalOutcome {
+    Solved(SudokuSolution),
+    /// Inference made no more progress; contains everything it could deduce
+    Stuck(SudokuPossibilities),
+}
+
+/// Candidates of a cell in the order they are tried. A cell has at most 9,
+/// so they are kept inline instead of allocating at every guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct GuessOrder {
+    digits: [Digit; 9],
+    len: usize,
+}
+
+impl GuessOrder {
+    /// The candidates in ascending order
+    fn new(candidates: PossibleValues) -> Self {
+        let mut digits = [Digit::MIN; 9];
+        for (slot, digit) in digits.iter_mut().zip(candidates) {
+            *slot = digit;
+        }
+        Self {
+            digits,
+            len: candidates.count() as usize,
         }
     }
 }
 
-#[must_use]
-pub struct Broken;
+impl Deref for GuessOrder {
+    type Target = [Digit];
 
-pub enum CannotSolve {
-    Broken,
-    DepthLimit(SudokuPossibilities),
+    fn deref(&self) -> &[Digit] {
+        &self.digits[..self.len]
+    }
 }
-impl From<Broken> for CannotSolve {
-    fn from(_: Broken) -> Self {
-        Self::Broken
+
+impl DerefMut for GuessOrder {
+    fn deref_mut(&mut self) -> &mut [Digit] {
+        &mut self.digits[..self.len]
+    }
+}
+
+/// How many cells of each unit still allow each digit, so that hidden
+/// singles are noticed when a count drops to 1 instead of by scanning
+struct DigitCounts {
+    counts: [[u8; 9]; 27],
+    /// Unit indices and digits whose count has dropped to 1
+    singles: Vec<(usize, Digit)>,
+}
+
+impl DigitCounts {
+    fn new(sp: &SudokuPossibilities, units: &Units) -> Self {
+        let mut counts = [[0; 9]; 27];
+        for (i, cell) in sp.cells().iter().enumerate() {
+            for unit in units.of((i / 9, i % 9)) {
+                for digit in *cell {
+                    counts[unit][digit.index() as usize] += 1;
+                }
+            }
+        }
+        let mut singles = Vec::new();
+        for (unit, unit_counts) in counts.iter().enumerate() {
+            for (d, &count) in unit_counts.iter().enumerate() {
+                if count == 1 {
+                    singles.push((unit, Digit::from_index(d as u8).unwrap()));
+                }
+            }
+        }
+        Self { counts, singles }
+    }
+
+    /// Account for `removed` no longer being candidates of `cell`
+    fn remove(&mut self, units: &Units, cell: (usize, usize), removed: PossibleValues) {
+        for unit in units.of(cell) {
+            for digit in removed {
+                let count = &mut self.counts[unit][digit.index() as usize];
+                *count -= 1;
+                if *count == 1 {
+                    self.singles.push((unit, digit));
+                }
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Digit;
-
     use super::*;
+    use crate::{CancellationToken, Step, strategy::CustomStrategy};
+
     #[test]
     fn possibilities_broken_simple() {
         let mut sp = SudokuPossibilities::EMPTY;
@@ -217,6 +631,198 @@ mod tests {
         assert!(sp.is_broken());
     }
 
+    #[test]
+    fn propagate_cascades() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let one = PossibleValues::from(Digit::unchecked(1));
+        let two = PossibleValues::from(Digit::unchecked(2));
+        sp.grid[0][0] = one;
+        sp.grid[0][5] = one | two;
+        assert!(sp.propagate().is_ok());
+        assert_eq!(sp.grid[0][5], two);
+        assert!(!sp.grid[8][5].contains(Digit::unchecked(2)));
+        assert!(sp.grid[8][5].contains(Digit::unchecked(1)));
+
+        sp.grid[4][5] = two;
+        assert!(sp.propagate().is_err());
+
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.grid[3][3] = two;
+        sp.grid[4][4] = one | two;
+        sp.grid[4][8] = one;
+        // (4, 4) becomes a second two in the box of (3, 3)
+        assert_eq!(
+            sp.propagate(),
+            Err(Conflict::Duplicate {
+                cells: [(3, 3), (4, 4)],
+                digit: Digit::unchecked(2)
+            })
+        );
+    }
+
+    #[test]
+    fn propagate_places_hidden_singles() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let five = Digit::unchecked(5);
+        for c in 0..8 {
+            sp.grid[2][c].remove(five);
+        }
+        sp.propagate().unwrap();
+        assert_eq!(sp.grid[2][8].determined(), None);
+
+        sp.propagate_from(Bitboard::ALL, true, &CLASSIC).unwrap();
+        assert_eq!(sp.grid[2][8].determined(), Some(five));
+        assert!(!sp.grid[5][8].contains(five));
+    }
+
+    #[test]
+    fn dirty_rounds_match_full_rounds() {
+        let data = std::fs::read_to_string("puzzles/7gJb9G8fRt.txt").expect("Missing puzzle");
+        let start = SudokuPossibilities::from(Sudoku::parse(&data).unwrap());
+        let config = SolverConfig::default();
+
+        let mut full = start;
+        loop {
+            let original = full;
+            full.infer_step(&config).unwrap();
+            if full == original {
+                break;
+            }
+        }
+        let mut dirty = start;
+        dirty.infer(&config).unwrap();
+        assert_eq!(dirty, full);
+    }
+
+    #[test]
+    fn cancelled_during_inference() {
+        let data = std::fs::read_to_string("puzzles/7gJb9G8fRt.txt").expect("Missing puzzle");
+        let sudoku = Sudoku::parse(&data).unwrap();
+        // Inference alone solves it, in many rounds
+        assert!(matches!(
+            sudoku.solve_logical(),
+            Some(LogicalOutcome::Solved(_))
+        ));
+
+        let cancelling = || {
+            let token = CancellationToken::new();
+            let cancel = token.clone();
+            let mut config = SolverConfig {
+                cancellation: Some(token),
+                ..Default::default()
+            };
+            config.strategies.insert(
+                0,
+                CustomStrategy::new("Cancel", move |_: &SudokuPossibilities| {
+                    cancel.cancel();
+                    Vec::new()
+                }),
+            );
+            config
+        };
+        assert!(matches!(
+            sudoku.try_solve_with(&cancelling()),
+            Err(SolveError::Cancelled(_))
+        ));
+
+        let mut search = Search::new(SudokuPossibilities::from(sudoku));
+        assert_eq!(search.step(&cancelling()), Step::Stopped);
+        assert_eq!(search.pending().len(), 1);
+    }
+
+    #[test]
+    fn branch_on_fewest_candidates() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        assert_eq!(sp.branch_cell(), Some((0, 0)));
+
+        sp.grid[6][2] =
+            PossibleValues::from(Digit::unchecked(1)) | PossibleValues::from(Digit::unchecked(2));
+        sp.grid[4][7] = sp.grid[6][2];
+        sp.grid[4][8] = PossibleValues::from(Digit::unchecked(3));
+        // Both have two candidates, but (4, 8) is already solved next to (4, 7)
+        assert_eq!(sp.branch_cell(), Some((6, 2)));
+
+        sp.grid[6][2] = PossibleValues::from(Digit::unchecked(1));
+        assert_eq!(sp.branch_cell(), Some((4, 7)));
+    }
+
+    #[test]
+    fn value_orders() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let digits = |values: GuessOrder| values.iter().map(|d| d.index() + 1).collect::<Vec<_>>();
+        // 3 is placed twice and 5 once; 2 and 3 are ruled out in most of row 0
+        sp.grid[5][5] = PossibleValues::from(Digit::unchecked(3));
+        sp.grid[7][7] = PossibleValues::from(Digit::unchecked(3));
+        sp.grid[6][6] = PossibleValues::from(Digit::unchecked(5));
+        for c in 1..9 {
+            sp.grid[0][c].remove(Digit::unchecked(2));
+            sp.grid[0][c].remove(Digit::unchecked(3));
+        }
+        let cell = (0, 0);
+
+        let ascending = sp.ordered_values(cell, ValueOrder::Ascending);
+        assert_eq!(digits(ascending), (1..=9).collect::<Vec<_>>());
+        let least = sp.ordered_values(cell, ValueOrder::LeastConstraining);
+        assert_eq!(digits(least)[..2], [2, 3]);
+        let frequency = sp.ordered_values(cell, ValueOrder::Frequency);
+        assert_eq!(digits(frequency)[7..], [5, 3]);
+
+        let random = sp.ordered_values(cell, ValueOrder::Random { seed: 7 });
+        assert_eq!(
+            random,
+            sp.ordered_values(cell, ValueOrder::Random { seed: 7 })
+        );
+        assert_ne!(random, ascending);
+        let mut sorted = random;
+        sorted.sort_by_key(|d| d.index());
+        assert_eq!(sorted, ascending);
+    }
+
+    #[test]
+    fn siblings_stop_after_solution() {
+        // Searching every branch of an empty grid would never finish
+        for deterministic in [true, false] {
+            let config = SolverConfig {
+                deterministic,
+                parallel_depth: 2,
+                ..SolverConfig::basic()
+            };
+            let solution = SudokuPossibilities::EMPTY.solve(&config).unwrap();
+            assert!(Sudoku::splat(None).is_solution(solution.grid()));
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_fan_out() {
+        use crate::Parallelism;
+        use std::sync::Arc;
+
+        let data = std::fs::read_to_string("puzzles/7gJb9G8fRt.txt").expect("Missing puzzle");
+        let sudoku = Sudoku::parse(&data).unwrap();
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+        let threads = Parallelism::threads(3).unwrap();
+        let Parallelism::Pool(built) = &threads else {
+            panic!("Thread counts are turned into a pool");
+        };
+        assert_eq!(built.current_num_threads(), 3);
+        for parallelism in [threads, Parallelism::Pool(Arc::new(pool))] {
+            let config = SolverConfig {
+                parallel_depth: 3,
+                parallelism,
+                ..SolverConfig::basic()
+            };
+            let outcome = SudokuPossibilities::from(sudoku)
+                .solve_with_stats(&config)
+                .unwrap();
+            assert_eq!(Some(outcome.solution), sudoku.solve());
+            assert!(outcome.stats.guesses > 0);
+        }
+    }
+
     #[test]
     fn possibilities_broken_cell() {
         let mut sp = SudokuPossibilities::EMPTY;
@@ -0,0 +1,35 @@
+use std::{ops::AddAssign, time::Duration};
+
+use crate::SudokuSolution;
+
+/// How much work a solve took
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    /// Candidates tried when inference got stuck
+    pub guesses: usize,
+    /// Guesses that turned out to be wrong
+    pub backtracks: usize,
+    /// Rounds of inference over all searched states
+    pub propagation_rounds: usize,
+    /// Most guesses in effect at the same time
+    pub max_depth: usize,
+    pub elapsed: Duration,
+}
+
+impl AddAssign for SolveStats {
+    /// Combine the work of searches done one after another or in parallel
+    fn add_assign(&mut self, other: Self) {
+        self.guesses += other.guesses;
+        self.backtracks += other.backtracks;
+        self.propagation_rounds += other.propagation_rounds;
+        self.max_depth = self.max_depth.max(other.max_depth);
+        self.elapsed += other.elapsed;
+    }
+}
+
+/// A solution together with the work it took to find it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolveOutcome {
+    pub solution: SudokuSolution,
+    pub stats: SolveStats,
+}
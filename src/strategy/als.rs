@@ -0,0 +1,135 @@
+use crate::{Digit, PossibleValues, SudokuPossibilities, Unit, Units};
+
+use super::{combinations, eliminate_seen_by};
+
+/// Largest almost locked set to look for. Bigger sets are rarely useful
+/// and their number grows quickly.
+const MAX_ALS_SIZE: usize = 4;
+
+/// Almost locked set: `n` unsolved cells within one unit that together
+/// have `n + 1` candidates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Als {
+    pub cells: Vec<(usize, usize)>,
+    pub digits: PossibleValues,
+}
+
+impl Als {
+    /// Cells of the set that can hold `digit`
+    pub fn cells_with(&self, sp: &SudokuPossibilities, digit: Digit) -> Vec<(usize, usize)> {
+        self.cells
+            .iter()
+            .copied()
+            .filter(|&(r, c)| sp.grid[r][c].contains(digit))
+            .collect()
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        self.cells.iter().any(|cell| other.cells.contains(cell))
+    }
+}
+
+/// All almost locked sets of up to `max_size` cells, each listed once
+pub fn find_als(sp: &SudokuPossibilities, units: &Units, max_size: usize) -> Vec<Als> {
+    let mut result: Vec<Als> = Vec::new();
+    for unit in Unit::ALL {
+        let open: Vec<(usize, usize)> = units
+            .cells(unit)
+            .into_iter()
+            .filter(|&(r, c)| sp.grid[r][c].count() >= 2)
+            .collect();
+        for size in 1..=max_size.min(open.len()) {
+            combinations(&open, size, &mut |cells| {
+                let digits = cells
+                    .iter()
+                    .fold(PossibleValues::EMPTY, |acc, &(r, c)| acc | sp.grid[r][c]);
+                if digits.count() as usize == size + 1
+                    && !result.iter().any(|als| als.cells == cells)
+                {
+                    result.push(Als {
+                        cells: cells.to_vec(),
+                        digits,
+                    });
+                }
+            });
+        }
+    }
+    result
+}
+
+/// ALS-XZ: two disjoint almost locked sets share a restricted common digit `x`,
+/// meaning every `x` of one set sees every `x` of the other. At most one of them
+/// can hold `x`, so the other is locked, and for any other shared digit `z`
+/// one of the sets must hold it. `z` is removed from cells seeing all its
+/// positions in both sets.
+pub fn als_xz(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    let sets = find_als(sp, units, MAX_ALS_SIZE);
+    let mut found = Vec::new();
+    for (i, a) in sets.iter().enumerate() {
+        for b in &sets[i + 1..] {
+            let common = a.digits & b.digits;
+            if common.count() < 2 || a.overlaps(b) {
+                continue;
+            }
+            for x in common {
+                let (ax, bx) = (a.cells_with(sp, x), b.cells_with(sp, x));
+                if !ax.iter().all(|&p| bx.iter().all(|&q| units.sees(p, q))) {
+                    continue;
+                }
+                for z in common {
+                    if z == x {
+                        continue;
+                    }
+                    let mut cells = a.cells_with(sp, z);
+                    cells.extend(b.cells_with(sp, z));
+                    found.push((cells, z));
+                }
+            }
+        }
+    }
+
+    let mut progress = false;
+    for (cells, z) in found {
+        progress |= eliminate_seen_by(sp, units, &cells, PossibleValues::from(z));
+    }
+    progress
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(digits: &[u8]) -> PossibleValues {
+        digits.iter().fold(PossibleValues::EMPTY, |acc, &d| {
+            acc | PossibleValues::from(Digit::unchecked(d))
+        })
+    }
+
+    #[test]
+    fn find_als_deduplicates() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.grid[0][0] = set(&[1, 2]);
+        sp.grid[0][1] = set(&[2, 3]);
+        let sets = find_als(&sp, Units::classic(), 2);
+        assert_eq!(sets.len(), 3);
+        assert!(sets.contains(&Als {
+            cells: vec![(0, 0), (0, 1)],
+            digits: set(&[1, 2, 3]),
+        }));
+    }
+
+    #[test]
+    fn als_xz_with_single_cell() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        // A = {(0, 0)} with {1, 2}, B = {(3, 0), (3, 1)} with {1, 2, 5}
+        sp.grid[0][0] = set(&[1, 2]);
+        sp.grid[3][0] = set(&[2, 5]);
+        sp.grid[3][1] = set(&[1, 5]);
+        assert!(als_xz(&mut sp, Units::classic()));
+        // x = 2 is restricted, so z = 1 is in (0, 0) or (3, 1)
+        let one = Digit::unchecked(1);
+        assert!(!sp.grid[0][1].contains(one));
+        assert!(!sp.grid[2][1].contains(one));
+        assert!(sp.grid[4][1].contains(one));
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{Digit, PossibleValues, SudokuPossibilities, Units};
+
+use super::{
+    eliminate_seen_by,
+    links::{StrongLink, strong_links},
+};
+
+/// X-Chains: alternating strong and weak links on a single digit,
+/// starting and ending with a strong link. One of the two chain ends must hold
+/// the digit, so it is removed from every cell seeing both ends.
+/// Chains are limited to `max_length` links.
+pub fn x_chains(sp: &mut SudokuPossibilities, units: &Units, max_length: usize) -> bool {
+    let mut progress = false;
+    for d in 0..9 {
+        let digit = Digit::from_index(d).unwrap();
+        let strong = strong_links(sp, units, digit);
+        let cells: Vec<(usize, usize)> = (0..81)
+            .map(|i| (i / 9, i % 9))
+            .filter(|&(r, c)| sp.grid[r][c].contains(digit))
+            .collect();
+
+        let mut ends = Vec::new();
+        for &start in &cells {
+            let mut path = vec![start];
+            x_chain_search(units, &strong, &cells, max_length, &mut path, &mut ends);
+        }
+        for (a, b) in ends {
+            progress |= eliminate_seen_by(sp, units, &[a, b], PossibleValues::from(digit));
+        }
+    }
+    progress
+}
+
+fn x_chain_search(
+    units: &Units,
+    strong: &[StrongLink],
+    cells: &[(usize, usize)],
+    max_length: usize,
+    path: &mut Vec<(usize, usize)>,
+    ends: &mut Vec<StrongLink>,
+) {
+    let links = path.len() - 1;
+    let current = *path.last().unwrap();
+    if links % 2 == 1 && links >= 3 && path[0] < current {
+        ends.push((path[0], current));
+    }
+    if links == max_length {
+        return;
+    }
+
+    let next: Vec<(usize, usize)> = if links.is_multiple_of(2) {
+        strong
+            .iter()
+            .filter_map(|&(a, b)| {
+                if a == current {
+                    Some(b)
+                } else if b == current {
+                    Some(a)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    } else {
+        cells
+            .iter()
+            .copied()
+            .filter(|&cell| units.sees(cell, current))
+            .collect()
+    };
+    for cell in next {
+        if path.contains(&cell) {
+            continue;
+        }
+        path.push(cell);
+        x_chain_search(units, strong, cells, max_length, path, ends);
+        path.pop();
+    }
+}
+
+/// XY-Chains: a path of bivalue cells, each seeing the next, where the cell
+/// values are forced in turn if the first cell is not `z`. If the last cell is
+/// then forced to `z`, one of the two ends is `z` and it is removed from every
+/// cell seeing both ends. Chains are limited to `max_length` links.
+pub fn xy_chains(sp: &mut SudokuPossibilities, units: &Units, max_length: usize) -> bool {
+    let bivalue: Vec<(usize, usize)> = (0..81)
+        .map(|i| (i / 9, i % 9))
+        .filter(|&(r, c)| sp.grid[r][c].count() == 2)
+        .collect();
+
+    let mut found = Vec::new();
+    for &start in &bivalue {
+        for z in sp.grid[start.0][start.1] {
+            let mut path = vec![start];
+            xy_chain_search(sp, units, max_length, z, z, &mut path, &mut found);
+        }
+    }
+
+    let mut progress = false;
+    for ((a, b), z) in found {
+        progress |= eliminate_seen_by(sp, units, &[a, b], PossibleValues::from(z));
+    }
+    progress
+}
+
+/// Extend the chain assuming its last cell is not `excluded`
+fn xy_chain_search(
+    sp: &SudokuPossibilities,
+    units: &Units,
+    max_length: usize,
+    z: Digit,
+    excluded: Digit,
+    path: &mut Vec<(usize, usize)>,
+    found: &mut Vec<(StrongLink, Digit)>,
+) {
+    let current = *path.last().unwrap();
+    let mut value = sp.grid[current.0][current.1];
+    value.remove(excluded);
+    let Some(value) = value.determined() else {
+        return;
+    };
+    if path.len() > 2 && value == z && path[0] < current {
+        found.push(((path[0], current), z));
+    }
+    if path.len() > max_length {
+        return;
+    }
+
+    for &next in units.peers(current) {
+        let candidates = sp.grid[next.0][next.1];
+        if path.contains(&next) || candidates.count() != 2 || !candidates.contains(value) {
+            continue;
+        }
+        path.push(next);
+        xy_chain_search(sp, units, max_length, z, value, path, found);
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn x_chain_of_five_links() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let seven = Digit::unchecked(7);
+        // (0, 0) = (6, 0) - (6, 4) = (4, 4) - (4, 8) = (2, 8)
+        for r in 0..9 {
+            if r != 0 && r != 6 {
+                sp.grid[r][0].remove(seven);
+            }
+            if r != 6 && r != 4 {
+                sp.grid[r][4].remove(seven);
+            }
+            if r != 4 && r != 2 {
+                sp.grid[r][8].remove(seven);
+            }
+        }
+        let mut short = sp;
+        x_chains(&mut short, Units::classic(), 3);
+        assert!(short.grid[0][6].contains(seven));
+
+        assert!(x_chains(&mut sp, Units::classic(), 5));
+        // Common peers of (0, 0) and (2, 8)
+        for (r, c) in [(0, 6), (0, 7), (2, 1), (2, 2)] {
+            assert!(!sp.grid[r][c].contains(seven));
+        }
+        assert!(sp.grid[1][6].contains(seven));
+    }
+
+    #[test]
+    fn xy_chain_of_four_cells() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let set = |a, b| {
+            PossibleValues::from(Digit::unchecked(a)) | PossibleValues::from(Digit::unchecked(b))
+        };
+        // {1, 2} (0, 0) - {2, 3} (0, 5) - {3, 4} (5, 5) - {4, 1} (5, 7)
+        sp.grid[0][0] = set(1, 2);
+        sp.grid[0][5] = set(2, 3);
+        sp.grid[5][5] = set(3, 4);
+        sp.grid[5][7] = set(4, 1);
+        let one = Digit::unchecked(1);
+
+        let mut short = sp;
+        xy_chains(&mut short, Units::classic(), 2);
+        assert!(short.grid[5][0].contains(one));
+
+        assert!(xy_chains(&mut sp, Units::classic(), 3));
+        // Common peers of (0, 0) and (5, 7)
+        assert!(!sp.grid[5][0].contains(one));
+        assert!(!sp.grid[0][7].contains(one));
+        assert!(sp.grid[5][1].contains(one));
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{Bitboard, Digit, PossibleValues, SudokuPossibilities, Units};
+
+use super::links::strong_links;
+
+/// A single candidate: a digit in a cell
+type Candidate = ((usize, usize), Digit);
+
+/// A connected group of nodes joined by strong links, two-colored so that
+/// the ends of every link differ. Exactly one of the colors is true.
+pub struct Cluster<T> {
+    pub cells: Vec<(T, bool)>,
+}
+
+impl<T: Copy + PartialEq> Cluster<T> {
+    pub fn color(&self, color: bool) -> impl Iterator<Item = T> + '_ {
+        self.cells
+            .iter()
+            .filter(move |(_, c)| *c == color)
+            .map(|(cell, _)| *cell)
+    }
+}
+
+/// Two-color the connected components of a strong link graph
+pub fn clusters<T: Copy + PartialEq>(links: &[(T, T)]) -> Vec<Cluster<T>> {
+    let mut result: Vec<Cluster<T>> = Vec::new();
+    for &(start, _) in links {
+        if result
+            .iter()
+            .any(|cl| cl.cells.iter().any(|(c, _)| *c == start))
+        {
+            continue;
+        }
+        let mut cells = vec![(start, true)];
+        let mut i = 0;
+        while i < cells.len() {
+            let (cell, color) = cells[i];
+            for &(a, b) in links {
+                let other = if a == cell {
+                    b
+                } else if b == cell {
+                    a
+                } else {
+                    continue;
+                };
+                if !cells.iter().any(|(c, _)| *c == other) {
+                    cells.push((other, !color));
+                }
+            }
+            i += 1;
+        }
+        result.push(Cluster { cells });
+    }
+    result
+}
+
+/// Simple coloring on each digit's strong link clusters:
+/// * if two cells of one color see each other, that color is false everywhere
+/// * a cell that sees both colors of a cluster cannot hold the digit
+pub fn simple_coloring(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    let mut progress = false;
+    for d in 0..9 {
+        let digit = Digit::from_index(d).unwrap();
+        for cluster in clusters(&strong_links(sp, units, digit)) {
+            for color in [true, false] {
+                let same: Vec<_> = cluster.color(color).collect();
+                let wrap = same
+                    .iter()
+                    .enumerate()
+                    .any(|(i, &a)| same[i + 1..].iter().any(|&b| units.sees(a, b)));
+                if wrap {
+                    for (r, c) in same {
+                        sp.grid[r][c].remove(digit);
+                    }
+                    progress = true;
+                }
+            }
+
+            let colored = cells(cluster.cells.iter().map(|&(cell, _)| cell));
+            let seen = |color| seen_by(units, cluster.color(color));
+            let remove = sp.positions(digit) & !colored & seen(true) & seen(false);
+            for (r, c) in remove.cells() {
+                sp.grid[r][c].remove(digit);
+                progress = true;
+            }
+        }
+    }
+    progress
+}
+
+/// The given cells as a [`Bitboard`]
+fn cells(cells: impl Iterator<Item = (usize, usize)>) -> Bitboard {
+    cells.fold(Bitboard::EMPTY, |board, cell| board | Bitboard::cell(cell))
+}
+
+/// Cells that see at least one of the given cells
+fn seen_by(units: &Units, cells: impl Iterator<Item = (usize, usize)>) -> Bitboard {
+    cells.fold(Bitboard::EMPTY, |board, cell| {
+        board | units.peer_board(cell)
+    })
+}
+
+/// Strong links between candidates: conjugate pairs of every digit
+/// and the two candidates of every bivalue cell
+fn medusa_links(sp: &SudokuPossibilities, units: &Units) -> Vec<(Candidate, Candidate)> {
+    let mut links = Vec::new();
+    for d in 0..9 {
+        let digit = Digit::from_index(d).unwrap();
+        for (a, b) in strong_links(sp, units, digit) {
+            links.push(((a, digit), (b, digit)));
+        }
+    }
+    for r in 0..9 {
+        for c in 0..9 {
+            if let [x, y] = sp.grid[r][c].options()[..] {
+                links.push((((r, c), x), ((r, c), y)));
+            }
+        }
+    }
+    links
+}
+
+/// 3D Medusa: coloring across all candidates, linked both within units
+/// and within bivalue cells.
+///
+/// A color is false if it appears twice in a cell or twice for a digit in a unit.
+/// An uncolored candidate is removed if its cell holds both colors,
+/// if it sees the digit in both colors, or if it sees the digit in one color
+/// while its cell holds the other color.
+pub fn medusa(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    let mut progress = false;
+    for cluster in clusters(&medusa_links(sp, units)) {
+        let mut contradiction = false;
+        for color in [true, false] {
+            let same: Vec<Candidate> = cluster.color(color).collect();
+            let twice = same.iter().enumerate().any(|(i, &(a, da))| {
+                same[i + 1..]
+                    .iter()
+                    .any(|&(b, db)| a == b || (da == db && units.sees(a, b)))
+            });
+            if twice {
+                for ((r, c), digit) in same {
+                    sp.grid[r][c].remove(digit);
+                }
+                progress = true;
+                contradiction = true;
+            }
+        }
+        if contradiction {
+            continue;
+        }
+
+        let boards = sp.digit_boards();
+        let of_digit = |color, digit| {
+            cluster
+                .color(color)
+                .filter(move |&(_, d)| d == digit)
+                .map(|(cell, _)| cell)
+        };
+        // Cells still holding a candidate of each color
+        let in_cell = |color| {
+            cells(
+                cluster
+                    .color(color)
+                    .filter(|&(cell, d)| boards[d.index() as usize].contains(cell))
+                    .map(|(cell, _)| cell),
+            )
+        };
+        let (has_true, has_false) = (in_cell(true), in_cell(false));
+        for digit in PossibleValues::ANY {
+            let colored = cells(of_digit(true, digit).chain(of_digit(false, digit)));
+            let seen = |color| seen_by(units, of_digit(color, digit));
+            let (seen_true, seen_false) = (seen(true), seen(false));
+            let remove = boards[digit.index() as usize]
+                & !colored
+                & ((has_true & has_false)
+                    | (seen_true & seen_false)
+                    | (has_true & seen_false)
+                    | (has_false & seen_true));
+            for (r, c) in remove.cells() {
+                sp.grid[r][c].remove(digit);
+                progress = true;
+            }
+        }
+    }
+    progress
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PossibleValues, unit::Unit};
+
+    use super::*;
+
+    /// Leave `digit` only in the given cells of row `r`
+    fn only_in_row(sp: &mut SudokuPossibilities, digit: Digit, r: usize, cols: &[usize]) {
+        for c in 0..9 {
+            if !cols.contains(&c) {
+                sp.grid[r][c].remove(digit);
+            }
+        }
+    }
+
+    /// Leave `digit` only in the given cells of column `c`
+    fn only_in_col(sp: &mut SudokuPossibilities, digit: Digit, c: usize, rows: &[usize]) {
+        for r in 0..9 {
+            if !rows.contains(&r) {
+                sp.grid[r][c].remove(digit);
+            }
+        }
+    }
+
+    #[test]
+    fn color_trap() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let one = Digit::unchecked(1);
+        // Chain (0, 0) - (0, 4) - (6, 4) - (8, 5)
+        only_in_row(&mut sp, one, 0, &[0, 4]);
+        only_in_col(&mut sp, one, 4, &[0, 6]);
+        for (r, c) in Unit::Box(7).cells() {
+            if (r, c) != (6, 4) && (r, c) != (8, 5) {
+                sp.grid[r][c].remove(one);
+            }
+        }
+        assert!(simple_coloring(&mut sp, Units::classic()));
+        // (8, 0) sees (0, 0) and (8, 5), which have opposite colors
+        assert!(!sp.grid[8][0].contains(one));
+        assert!(sp.grid[7][0].contains(one));
+    }
+
+    #[test]
+    fn color_wrap() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let one = Digit::unchecked(1);
+        // Chain (0, 0) - (0, 4) - (7, 4) - (7, 1) - (2, 1)
+        only_in_row(&mut sp, one, 0, &[0, 4]);
+        only_in_col(&mut sp, one, 4, &[0, 7]);
+        only_in_row(&mut sp, one, 7, &[1, 4]);
+        only_in_col(&mut sp, one, 1, &[2, 7]);
+        // (0, 0) and (2, 1) share box 0 and have the same color
+        assert!(simple_coloring(&mut sp, Units::classic()));
+        assert!(!sp.grid[0][0].contains(one));
+        assert!(!sp.grid[2][1].contains(one));
+        assert!(!sp.grid[7][4].contains(one));
+        assert!(sp.grid[0][4].contains(one));
+        assert!(sp.grid[7][1].contains(one));
+    }
+
+    #[test]
+    fn medusa_through_bivalue_cells() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let (one, two) = (Digit::unchecked(1), Digit::unchecked(2));
+        let pair = PossibleValues::from(one) | PossibleValues::from(two);
+        // 1 at (0, 0) - 2 at (0, 0) - 2 at (5, 0) - 1 at (5, 0)
+        sp.grid[0][0] = pair;
+        sp.grid[5][0] = pair;
+        only_in_col(&mut sp, two, 0, &[0, 5]);
+        assert!(medusa(&mut sp, Units::classic()));
+        // Either (0, 0) or (5, 0) is 1
+        assert!(!sp.grid[3][0].contains(one));
+        assert!(!sp.grid[8][0].contains(one));
+        assert!(sp.grid[3][1].contains(one));
+    }
+}
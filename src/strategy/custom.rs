@@ -0,0 +1,85 @@
+use crate::{Digit, SudokuPossibilities};
+
+use super::{Strategy, StrategyResult};
+
+/// A candidate ruled out by a strategy: the digit can't go in the cell
+pub type Elimination = ((usize, usize), Digit);
+
+/// A strategy defined by a closure that lists the candidates to remove.
+/// Eliminations of candidates that are already gone are ignored.
+pub struct CustomStrategy<F> {
+    name: String,
+    difficulty: u32,
+    find: F,
+}
+
+impl<F> CustomStrategy<F>
+where
+    F: Fn(&SudokuPossibilities) -> Vec<Elimination> + Send + Sync,
+{
+    pub fn new(name: impl Into<String>, find: F) -> Self {
+        Self {
+            name: name.into(),
+            difficulty: 50,
+            find,
+        }
+    }
+
+    /// Set the difficulty used for rating, 50 by default
+    pub fn with_difficulty(mut self, difficulty: u32) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+}
+
+impl<F> Strategy for CustomStrategy<F>
+where
+    F: Fn(&SudokuPossibilities) -> Vec<Elimination> + Send + Sync,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn difficulty(&self) -> u32 {
+        self.difficulty
+    }
+
+    fn apply(&self, sp: &mut SudokuPossibilities) -> StrategyResult {
+        let mut progress = false;
+        for ((r, c), digit) in (self.find)(sp) {
+            if sp.grid[r][c].contains(digit) {
+                sp.grid[r][c].remove(digit);
+                progress = true;
+            }
+        }
+        progress.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Rating, SolverConfig};
+
+    #[test]
+    fn custom_strategy_in_pipeline() {
+        // Corners may not hold a nine
+        let corners = CustomStrategy::new("No Corner Nines", |sp: &SudokuPossibilities| {
+            [(0, 0), (0, 8), (8, 0), (8, 8)]
+                .into_iter()
+                .filter(|&(r, c)| sp.grid[r][c].count() > 1)
+                .map(|cell| (cell, Digit::unchecked(9)))
+                .collect()
+        })
+        .with_difficulty(5);
+
+        let mut config = SolverConfig::basic();
+        config.strategies.insert(0, corners);
+        let mut sp = SudokuPossibilities::EMPTY;
+        let mut rating = Rating::default();
+        assert!(sp.infer_rated(&config, &mut rating).is_ok());
+        assert!(!sp.grid[8][0].contains(Digit::unchecked(9)));
+        assert_eq!(rating.count("No Corner Nines"), 1);
+        assert_eq!(rating.score(), 5);
+    }
+}
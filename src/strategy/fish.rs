@@ -0,0 +1,268 @@
+use crate::{Bitboard, PossibleValues, SudokuPossibilities, Unit, Units};
+
+use super::combinations;
+
+/// Line with the given index: a row, or with `transpose` a column
+fn line(transpose: bool, index: usize) -> Unit {
+    if transpose {
+        Unit::Col(index)
+    } else {
+        Unit::Row(index)
+    }
+}
+
+/// Cells of the lines whose indices are set in `mask`
+fn lines(transpose: bool, mask: u16) -> Bitboard {
+    (0..9)
+        .filter(|&i| mask & (1 << i) != 0)
+        .fold(Bitboard::EMPTY, |board, i| {
+            board | Bitboard::unit(line(transpose, i))
+        })
+}
+
+/// Bitmask of cover line indices on each base line where `places` has a
+/// cell. With `transpose` the base lines are columns instead of rows.
+fn cover_masks(places: Bitboard, transpose: bool) -> [u16; 9] {
+    let mut masks = [0; 9];
+    for (r, c) in places.cells() {
+        let (base, cover) = if transpose { (c, r) } else { (r, c) };
+        masks[base] |= 1 << cover;
+    }
+    masks
+}
+
+/// Basic fish of the given size: if a digit is confined to the same `size`
+/// columns in `size` rows (or vice versa), it can be removed from those
+/// columns in every other row.
+pub fn basic_fish(sp: &mut SudokuPossibilities, size: usize) -> bool {
+    let boards = sp.digit_boards();
+    let mut progress = false;
+    for digit in PossibleValues::ANY {
+        let mut places = boards[digit.index() as usize];
+        for transpose in [false, true] {
+            let masks = cover_masks(places, transpose);
+            let bases: Vec<usize> = (0..9)
+                .filter(|&base| (2..=size).contains(&(masks[base].count_ones() as usize)))
+                .collect();
+
+            let mut remove = Bitboard::EMPTY;
+            combinations(&bases, size, &mut |subset| {
+                let covers = subset.iter().fold(0, |acc, &base| acc | masks[base]);
+                if covers.count_ones() as usize == size {
+                    let base_lines =
+                        lines(transpose, subset.iter().fold(0, |acc, &b| acc | 1 << b));
+                    remove |= places & lines(!transpose, covers) & !base_lines;
+                }
+            });
+
+            for (r, c) in remove.cells() {
+                sp.grid[r][c].remove(digit);
+                progress = true;
+            }
+            places &= !remove;
+        }
+    }
+    progress
+}
+
+/// Finned fish of the given size: as a basic fish, except that some base line
+/// candidates ("fins") lie outside the cover lines, all within one box or
+/// region.
+/// Either the fish holds or a fin is true, so the digit can only be removed
+/// from cover line cells inside the fin box. Sashimi fish, where a base line
+/// has a single candidate left in the cover lines, are included.
+pub fn finned_fish(sp: &mut SudokuPossibilities, units: &Units, size: usize) -> bool {
+    let boards = sp.digit_boards();
+    let mut progress = false;
+    for digit in PossibleValues::ANY {
+        let mut places = boards[digit.index() as usize];
+        for transpose in [false, true] {
+            let masks = cover_masks(places, transpose);
+            // A fin box meets at most three cover indices, so no base line
+            // can have more than `size + 3` positions. Jigsaw regions can
+            // meet more, but those fish are rare enough to leave out.
+            let bases: Vec<usize> = (0..9)
+                .filter(|&base| (2..=size + 3).contains(&(masks[base].count_ones() as usize)))
+                .collect();
+
+            let mut remove = Bitboard::EMPTY;
+            combinations(&bases, size, &mut |subset| {
+                let union = subset.iter().fold(0, |acc, &base| acc | masks[base]);
+                let indices: Vec<usize> = (0..9).filter(|&i| union & (1 << i) != 0).collect();
+                if indices.len() <= size || indices.len() > size + 3 {
+                    return;
+                }
+                let base_lines = lines(transpose, subset.iter().fold(0, |acc, &b| acc | 1 << b));
+                combinations(&indices, size, &mut |covers| {
+                    let covers = covers.iter().fold(0u16, |acc, &c| acc | (1 << c));
+                    let cover_lines = lines(!transpose, covers);
+                    // Every base line needs a candidate in the covers
+                    if !subset.iter().all(|&base| masks[base] & covers != 0) {
+                        return;
+                    }
+                    let mut fins = (places & base_lines & !cover_lines).cells();
+                    let fin_box = units.region_of(fins.next().unwrap());
+                    if fins.all(|fin| units.region_of(fin) == fin_box) {
+                        let fin_box = units.board(Unit::Box(fin_box));
+                        remove |= places & cover_lines & fin_box & !base_lines;
+                    }
+                });
+            });
+
+            for (r, c) in remove.cells() {
+                sp.grid[r][c].remove(digit);
+                progress = true;
+            }
+            places &= !remove;
+        }
+    }
+    progress
+}
+
+pub fn finned_x_wing(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    finned_fish(sp, units, 2)
+}
+
+pub fn finned_swordfish(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    finned_fish(sp, units, 3)
+}
+
+pub fn x_wing(sp: &mut SudokuPossibilities) -> bool {
+    basic_fish(sp, 2)
+}
+
+pub fn swordfish(sp: &mut SudokuPossibilities) -> bool {
+    basic_fish(sp, 3)
+}
+
+pub fn jellyfish(sp: &mut SudokuPossibilities) -> bool {
+    basic_fish(sp, 4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Digit;
+
+    /// Cell at the given base line and cover line index.
+    /// With `transpose` the base lines are columns instead of rows.
+    fn cell(transpose: bool, base: usize, cover: usize) -> (usize, usize) {
+        if transpose {
+            (cover, base)
+        } else {
+            (base, cover)
+        }
+    }
+
+    /// Restrict `digit` within each of the given lines to the given cover lines
+    fn confine(
+        sp: &mut SudokuPossibilities,
+        digit: u8,
+        transpose: bool,
+        lines: &[(usize, &[usize])],
+    ) {
+        let digit = Digit::unchecked(digit);
+        for &(base, covers) in lines {
+            for cover in 0..9 {
+                if !covers.contains(&cover) {
+                    let (r, c) = cell(transpose, base, cover);
+                    sp.grid[r][c].remove(digit);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn x_wing_rows() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        confine(&mut sp, 5, false, &[(1, &[2, 7]), (6, &[2, 7])]);
+        assert!(x_wing(&mut sp));
+        let five = Digit::unchecked(5);
+        assert!(!sp.grid[0][2].contains(five));
+        assert!(!sp.grid[8][7].contains(five));
+        assert!(sp.grid[1][2].contains(five));
+        assert!(sp.grid[6][7].contains(five));
+        assert!(sp.grid[0][3].contains(five));
+        assert!(!x_wing(&mut sp));
+    }
+
+    #[test]
+    fn x_wing_columns() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        confine(&mut sp, 9, true, &[(0, &[4, 8]), (5, &[4, 8])]);
+        assert!(x_wing(&mut sp));
+        let nine = Digit::unchecked(9);
+        assert!(!sp.grid[4][3].contains(nine));
+        assert!(!sp.grid[8][6].contains(nine));
+        assert!(sp.grid[4][0].contains(nine));
+    }
+
+    #[test]
+    fn swordfish_with_two_candidate_lines() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        confine(
+            &mut sp,
+            2,
+            false,
+            &[(0, &[1, 4]), (3, &[4, 8]), (7, &[1, 4, 8])],
+        );
+        assert!(!x_wing(&mut sp));
+        assert!(swordfish(&mut sp));
+        let two = Digit::unchecked(2);
+        for r in [1, 2, 4, 5, 6, 8] {
+            for c in [1, 4, 8] {
+                assert!(!sp.grid[r][c].contains(two));
+            }
+            assert!(sp.grid[r][0].contains(two));
+        }
+        assert!(sp.grid[3][4].contains(two));
+    }
+
+    #[test]
+    fn jellyfish_columns() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        confine(
+            &mut sp,
+            7,
+            true,
+            &[(0, &[0, 3]), (2, &[3, 5]), (4, &[5, 8]), (8, &[0, 8])],
+        );
+        assert!(!swordfish(&mut sp));
+        assert!(jellyfish(&mut sp));
+        let seven = Digit::unchecked(7);
+        for r in [0, 3, 5, 8] {
+            assert!(!sp.grid[r][1].contains(seven));
+            assert!(!sp.grid[r][7].contains(seven));
+        }
+        assert!(sp.grid[0][0].contains(seven));
+        assert!(sp.grid[5][4].contains(seven));
+    }
+
+    #[test]
+    fn finned_x_wing_rows() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        // Row 1 has a fin at column 8 in box 2
+        confine(&mut sp, 4, false, &[(1, &[1, 6, 8]), (5, &[1, 6])]);
+        assert!(!x_wing(&mut sp));
+        assert!(finned_x_wing(&mut sp, Units::classic()));
+        let four = Digit::unchecked(4);
+        assert!(!sp.grid[0][6].contains(four));
+        assert!(!sp.grid[2][6].contains(four));
+        assert!(sp.grid[3][6].contains(four));
+        assert!(sp.grid[0][1].contains(four));
+        assert!(sp.grid[1][8].contains(four));
+    }
+
+    #[test]
+    fn sashimi_x_wing_columns() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        // Column 0 only has row 2 in the covers, the rest are fins in box 6
+        confine(&mut sp, 6, true, &[(0, &[2, 7, 8]), (4, &[2, 6])]);
+        assert!(finned_x_wing(&mut sp, Units::classic()));
+        let six = Digit::unchecked(6);
+        assert!(!sp.grid[6][1].contains(six));
+        assert!(!sp.grid[6][2].contains(six));
+        assert!(sp.grid[6][3].contains(six));
+        assert!(sp.grid[2][1].contains(six));
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt;
+
+use crate::{Digit, PossibleValues, SudokuPossibilities, Unit, Units};
+
+/// Why a forcing chain ended in a contradiction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Contradiction {
+    /// The cell has no candidates left
+    EmptyCell((usize, usize)),
+    /// The digit has no place left in the unit
+    NoPlace(Unit, Digit),
+}
+
+/// Assuming `assumption` leads through `implications` to a contradiction,
+/// so the assumed digit can be removed from its cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForcingChain {
+    pub assumption: ((usize, usize), Digit),
+    pub implications: Vec<((usize, usize), Digit)>,
+    pub contradiction: Contradiction,
+}
+
+impl SudokuPossibilities {
+    /// Find every candidate that leads to a contradiction within
+    /// `max_steps` forced placements, using only naked and hidden singles
+    /// in `units`.
+    pub fn forcing_chains(&self, units: &Units, max_steps: usize) -> Vec<ForcingChain> {
+        let mut chains = Vec::new();
+        for r in 0..9 {
+            for c in 0..9 {
+                if self.grid[r][c].count() < 2 {
+                    continue;
+                }
+                for digit in self.grid[r][c] {
+                    if let Some(chain) = self.assume(units, (r, c), digit, max_steps) {
+                        chains.push(chain);
+                    }
+                }
+            }
+        }
+        chains
+    }
+
+    /// Follow the singles forced by placing `digit` at `cell`
+    fn assume(
+        &self,
+        units: &Units,
+        cell: (usize, usize),
+        digit: Digit,
+        max_steps: usize,
+    ) -> Option<ForcingChain> {
+        let mut state = *self;
+        let mut implications = Vec::new();
+        let mut queue = vec![(cell, digit)];
+        state.grid[cell.0][cell.1] = PossibleValues::from(digit);
+
+        let contradiction = 'outer: loop {
+            while let Some((placed, value)) = queue.pop() {
+                for r in 0..9 {
+                    for c in 0..9 {
+                        if !units.sees(placed, (r, c)) || !state.grid[r][c].contains(value) {
+                            continue;
+                        }
+                        state.grid[r][c].remove(value);
+                        if state.grid[r][c].is_broken() {
+                            break 'outer Contradiction::EmptyCell((r, c));
+                        }
+                        if let Some(forced) = state.grid[r][c].determined() {
+                            implications.push(((r, c), forced));
+                            queue.push(((r, c), forced));
+                        }
+                    }
+                }
+            }
+
+            if implications.len() >= max_steps {
+                return None;
+            }
+
+            for unit in Unit::ALL {
+                for d in 0..9 {
+                    let d = Digit::from_index(d).unwrap();
+                    let mut places = units
+                        .cells(unit)
+                        .into_iter()
+                        .filter(|&(r, c)| state.grid[r][c].contains(d));
+                    match (places.next(), places.next()) {
+                        (None, _) => break 'outer Contradiction::NoPlace(unit, d),
+                        (Some((r, c)), None) if state.grid[r][c].count() > 1 => {
+                            state.grid[r][c] = PossibleValues::from(d);
+                            implications.push(((r, c), d));
+                            queue.push(((r, c), d));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if queue.is_empty() {
+                return None;
+            }
+        };
+
+        if implications.len() > max_steps {
+            return None;
+        }
+        Some(ForcingChain {
+            assumption: (cell, digit),
+            implications,
+            contradiction,
+        })
+    }
+}
+
+/// Nishio forcing chains: remove every candidate whose assumption leads
+/// to a contradiction within `max_steps` forced placements.
+pub fn forcing_chains(sp: &mut SudokuPossibilities, units: &Units, max_steps: usize) -> bool {
+    let chains = sp.forcing_chains(units, max_steps);
+    for chain in &chains {
+        let ((r, c), digit) = chain.assumption;
+        sp.grid[r][c].remove(digit);
+    }
+    !chains.is_empty()
+}
+
+/// Writes a cell as `r1c1`, counting from one
+struct CellName((usize, usize));
+
+impl fmt::Display for CellName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "r{}c{}", self.0.0 + 1, self.0.1 + 1)
+    }
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyCell(cell) => write!(f, "{} has no candidates", CellName(*cell)),
+            Self::NoPlace(unit, digit) => write!(f, "{digit} has no place in {unit:?}"),
+        }
+    }
+}
+
+impl fmt::Display for ForcingChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (cell, digit) = self.assumption;
+        write!(f, "{}={digit}", CellName(cell))?;
+        for &(cell, digit) in &self.implications {
+            write!(f, " => {}={digit}", CellName(cell))?;
+        }
+        write!(f, " => {}", self.contradiction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forcing_chain_to_empty_cell() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let set = |a, b| {
+            PossibleValues::from(Digit::unchecked(a)) | PossibleValues::from(Digit::unchecked(b))
+        };
+        // (0, 0) = 1 forces (0, 4) = 2, which forces (5, 4) = 3,
+        // leaving (5, 8) with nothing
+        sp.grid[0][0] = set(1, 4);
+        sp.grid[0][4] = set(1, 2);
+        sp.grid[5][4] = set(2, 3);
+        sp.grid[5][8] = PossibleValues::from(Digit::unchecked(3));
+
+        let chains = sp.forcing_chains(Units::classic(), 5);
+        let chain = chains
+            .iter()
+            .find(|chain| chain.assumption == ((0, 0), Digit::unchecked(1)))
+            .expect("No chain found");
+        assert_eq!(chain.contradiction, Contradiction::EmptyCell((5, 8)));
+        assert_eq!(
+            chain.to_string(),
+            "r1c1=1 => r1c5=2 => r6c5=3 => r6c9 has no candidates"
+        );
+
+        assert!(
+            sp.forcing_chains(Units::classic(), 1)
+                .iter()
+                .all(|ch| ch.assumption.0 != (0, 0))
+        );
+
+        assert!(forcing_chains(&mut sp, Units::classic(), 5));
+        assert_eq!(sp.grid[0][0], PossibleValues::from(Digit::unchecked(4)));
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{Digit, SudokuPossibilities, Unit, Units};
+
+/// Pairs of (box, line) units that share cells, with the rows of each box
+/// before its columns
+pub(super) fn box_line_pairs(units: &Units) -> Vec<(Unit, Unit)> {
+    let mut pairs = Vec::new();
+    for b in 0..9 {
+        let cells = units.cells(Unit::Box(b));
+        let mut lines: Vec<Unit> = cells.iter().map(|&(r, _)| Unit::Row(r)).collect();
+        lines.extend(cells.iter().map(|&(_, c)| Unit::Col(c)));
+        lines.sort_unstable_by_key(|line| line.index());
+        lines.dedup();
+        pairs.extend(lines.into_iter().map(|line| (Unit::Box(b), line)));
+    }
+    pairs
+}
+
+/// If every candidate position of a digit in one unit of a box-line pair
+/// lies inside the other, the digit must be placed in the intersection and
+/// can be removed from the rest of the other unit. With `from_line` the line
+/// is the one checked, otherwise the box.
+fn locked(sp: &mut SudokuPossibilities, units: &Units, from_line: bool) -> bool {
+    let pairs = box_line_pairs(units);
+    let mut progress = false;
+    for d in 0..9 {
+        let digit = Digit::from_index(d).unwrap();
+        let mut places = sp.positions(digit);
+        for &(bx, line) in &pairs {
+            let (from, to) = if from_line { (line, bx) } else { (bx, line) };
+            let (from, to) = (units.board(from), units.board(to));
+            let inside = places & from;
+            if inside.is_empty() || !(inside & !to).is_empty() {
+                continue;
+            }
+            let outside = places & to & !from;
+            for (r, c) in outside.cells() {
+                sp.grid[r][c].remove(digit);
+                progress = true;
+            }
+            places &= !outside;
+        }
+    }
+    progress
+}
+
+/// Pointing pairs/triples: candidates of a digit within a box all lie on one line,
+/// so the digit is eliminated from the rest of that line.
+pub fn pointing(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    locked(sp, units, false)
+}
+
+/// Box-line reduction: candidates of a digit within a line all lie in one box,
+/// so the digit is eliminated from the rest of that box.
+pub fn box_line_reduction(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    locked(sp, units, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pointing_pair_in_box() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let three = Digit::unchecked(3);
+        // In box 0, digit 3 is only possible in row 1
+        for (r, c) in Unit::Box(0).cells() {
+            if r != 1 {
+                sp.grid[r][c].remove(three);
+            }
+        }
+        assert!(pointing(&mut sp, Units::classic()));
+        assert!(sp.grid[1][0].contains(three));
+        assert!(!sp.grid[1][5].contains(three));
+        assert!(!sp.grid[1][8].contains(three));
+        assert!(sp.grid[0][5].contains(three));
+        assert!(!pointing(&mut sp, Units::classic()));
+    }
+
+    #[test]
+    fn box_line_reduction_in_column() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let eight = Digit::unchecked(8);
+        // In column 7, digit 8 is only possible in box 5
+        for r in 0..9 {
+            if !(3..6).contains(&r) {
+                sp.grid[r][7].remove(eight);
+            }
+        }
+        assert!(box_line_reduction(&mut sp, Units::classic()));
+        assert!(!sp.grid[4][6].contains(eight));
+        assert!(!sp.grid[3][8].contains(eight));
+        assert!(sp.grid[5][7].contains(eight));
+        assert!(sp.grid[4][0].contains(eight));
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{Digit, SudokuPossibilities, Unit, Units};
+
+/// Pair of cells that are the only two places for a digit in some unit.
+/// One of them must hold the digit.
+pub type StrongLink = ((usize, usize), (usize, usize));
+
+/// All strong links of `digit`, each listed once with the cells in order
+/// even if both cells share several units
+pub fn strong_links(sp: &SudokuPossibilities, units: &Units, digit: Digit) -> Vec<StrongLink> {
+    let positions = sp.positions(digit);
+    let mut links = Vec::new();
+    for unit in Unit::ALL {
+        let places = positions & units.board(unit);
+        if places.count() == 2 {
+            // Cells come in row-major order, so the link is ordered
+            let mut places = places.cells();
+            let link = (places.next().unwrap(), places.next().unwrap());
+            if !links.contains(&link) {
+                links.push(link);
+            }
+        }
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strong_links_deduplicated() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let four = Digit::unchecked(4);
+        for c in 2..9 {
+            sp.grid[0][c].remove(four);
+        }
+        // Row 0 and box 0 both link (0, 0) and (0, 1)
+        for (r, c) in Unit::Box(0).cells() {
+            if r != 0 {
+                sp.grid[r][c].remove(four);
+            }
+        }
+        assert_eq!(
+            strong_links(&sp, Units::classic(), four),
+            vec![((0, 0), (0, 1))]
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! Human-style deduction techniques.
+//!
+//! Each strategy removes candidates from a [`SudokuPossibilities`](crate::SudokuPossibilities)
+//! and reports whether it made any progress. The built-in techniques are listed in
+//! [`Technique`], and a [`StrategyRegistry`] decides which strategies run and in what order.
+
+mod als;
+mod chains;
+mod coloring;
+mod custom;
+mod fish;
+mod forcing;
+mod intersections;
+mod links;
+mod registry;
+mod single_digit;
+mod singles;
+mod subsets;
+mod sue_de_coq;
+mod technique;
+mod uniqueness;
+mod wings;
+
+use crate::{PossibleValues, SudokuPossibilities, Units};
+
+pub use als::als_xz;
+pub use chains::{x_chains, xy_chains};
+pub use coloring::{medusa, simple_coloring};
+pub use custom::{CustomStrategy, Elimination};
+pub use fish::{finned_swordfish, finned_x_wing, jellyfish, swordfish, x_wing};
+pub use forcing::{Contradiction, ForcingChain, forcing_chains};
+pub use intersections::{box_line_reduction, pointing};
+pub use registry::StrategyRegistry;
+pub use single_digit::{empty_rectangle, skyscraper, two_string_kite};
+pub use singles::hidden_singles;
+pub use subsets::{
+    hidden_pairs, hidden_quads, hidden_triples, naked_pairs, naked_quads, naked_triples,
+};
+pub use sue_de_coq::sue_de_coq;
+pub use technique::Technique;
+pub use uniqueness::{bug_plus_one, unique_rectangles};
+pub use wings::{w_wing, xy_wing, xyz_wing};
+
+/// Outcome of applying a strategy once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategyResult {
+    /// Some candidates were removed
+    Progress,
+    /// The strategy found nothing to remove
+    NoProgress,
+}
+
+impl From<bool> for StrategyResult {
+    fn from(progress: bool) -> Self {
+        if progress {
+            Self::Progress
+        } else {
+            Self::NoProgress
+        }
+    }
+}
+
+/// A deduction technique that removes candidates.
+/// Strategies must be sound: they may only remove candidates that cannot
+/// be part of any solution.
+pub trait Strategy: Send + Sync {
+    /// Human-readable name, such as "X-Wing"
+    fn name(&self) -> &str;
+
+    /// Whether the strategy is only sound on puzzles with a unique solution.
+    /// Such strategies are skipped unless
+    /// [`SolverConfig::assume_unique_solution`](crate::SolverConfig::assume_unique_solution) is set
+    /// and the puzzle has no rules beyond classic sudoku.
+    fn assumes_unique_solution(&self) -> bool {
+        false
+    }
+
+    /// How hard the strategy is for a human to spot, used to rate puzzles.
+    /// Hidden singles are 10 and X-Wing 30.
+    fn difficulty(&self) -> u32 {
+        50
+    }
+
+    fn apply(&self, sp: &mut SudokuPossibilities) -> StrategyResult;
+
+    /// [`apply`](Self::apply) on a grid whose boxes may have been replaced
+    /// with other regions, as given by `units`. Strategies that find the
+    /// regions through `units` override this. By default the strategy only
+    /// runs on classic units, as it may assume boxes.
+    fn apply_in(&self, sp: &mut SudokuPossibilities, units: &Units) -> StrategyResult {
+        if units.is_classic() {
+            self.apply(sp)
+        } else {
+            StrategyResult::NoProgress
+        }
+    }
+}
+
+/// Remove `digits` from every cell that sees all of `cells`.
+/// Returns true if any candidates were removed.
+fn eliminate_seen_by(
+    sp: &mut SudokuPossibilities,
+    units: &Units,
+    cells: &[(usize, usize)],
+    digits: PossibleValues,
+) -> bool {
+    let mut progress = false;
+    for r in 0..9 {
+        for c in 0..9 {
+            if !cells.iter().all(|&cell| units.sees(cell, (r, c))) {
+                continue;
+            }
+            let before = sp.grid[r][c];
+            sp.grid[r][c] &= !digits;
+            progress |= sp.grid[r][c] != before;
+        }
+    }
+    progress
+}
+
+/// Call `f` with every `k`-element subset of `items`, in lexicographic order
+fn combinations<T: Copy>(items: &[T], k: usize, f: &mut impl FnMut(&[T])) {
+    fn go<T: Copy>(items: &[T], k: usize, acc: &mut Vec<T>, f: &mut impl FnMut(&[T])) {
+        if acc.len() == k {
+            f(acc);
+            return;
+        }
+        for (i, &item) in items.iter().enumerate() {
+            if items.len() - i < k - acc.len() {
+                break;
+            }
+            acc.push(item);
+            go(&items[i + 1..], k, acc, f);
+            acc.pop();
+        }
+    }
+    go(items, k, &mut Vec::with_capacity(k), f);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combinations_count() {
+        let mut seen = Vec::new();
+        combinations(&[1, 2, 3, 4], 2, &mut |c| seen.push(c.to_vec()));
+        assert_eq!(seen.len(), 6);
+        assert_eq!(seen[0], vec![1, 2]);
+        assert_eq!(seen[5], vec![3, 4]);
+    }
+}
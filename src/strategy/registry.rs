@@ -0,0 +1,183 @@
+use std::{fmt, sync::Arc};
+
+use super::{Strategy, Technique};
+
+/// Chain length limit used by [`StrategyRegistry::standard`]
+const MAX_CHAIN_LENGTH: usize = 8;
+
+/// An ordered list of strategies to apply during inference
+#[derive(Clone, Default)]
+pub struct StrategyRegistry {
+    strategies: Vec<Arc<dyn Strategy>>,
+}
+
+impl StrategyRegistry {
+    /// A registry with no strategies
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every built-in technique, cheapest first. Fish are limited to Swordfish
+    /// and chains to 8 links, see [`extended`](Self::extended) for Jellyfish.
+    pub fn standard() -> Self {
+        use Technique::*;
+
+        [
+            HiddenSingles,
+            Pointing,
+            BoxLineReduction,
+            NakedPairs,
+            HiddenPairs,
+            NakedTriples,
+            HiddenTriples,
+            NakedQuads,
+            HiddenQuads,
+            XWing,
+            Skyscraper,
+            TwoStringKite,
+            EmptyRectangle,
+            FinnedXWing,
+            Swordfish,
+            FinnedSwordfish,
+            XyWing,
+            XyzWing,
+            WWing,
+            UniqueRectangles,
+            BugPlusOne,
+            SimpleColoring,
+            XChains(MAX_CHAIN_LENGTH),
+            XyChains(MAX_CHAIN_LENGTH),
+            Medusa,
+            AlsXz,
+            SueDeCoq,
+            ForcingChains(MAX_CHAIN_LENGTH),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    /// The standard techniques with Jellyfish after the Swordfish. They
+    /// rarely apply and are slow to look for.
+    pub fn extended() -> Self {
+        let mut registry = Self::standard();
+        let after = registry
+            .position(Technique::FinnedSwordfish.name())
+            .expect("Standard registry has Finned Swordfish")
+            + 1;
+        registry.insert(after, Technique::Jellyfish);
+        registry
+    }
+
+    /// Hidden singles and locked candidates only
+    pub fn basic() -> Self {
+        use Technique::*;
+
+        [HiddenSingles, Pointing, BoxLineReduction]
+            .into_iter()
+            .collect()
+    }
+
+    /// Add a strategy to the end of the list
+    pub fn register(&mut self, strategy: impl Strategy + 'static) {
+        self.strategies.push(Arc::new(strategy));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Strategy> {
+        self.strategies.iter().map(|strategy| strategy.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.strategies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strategies.is_empty()
+    }
+
+    /// Insert a strategy at `index`, shifting the later ones back
+    pub fn insert(&mut self, index: usize, strategy: impl Strategy + 'static) {
+        self.strategies.insert(index, Arc::new(strategy));
+    }
+
+    /// Remove every strategy with the given name.
+    /// Returns true if any were removed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.strategies.len();
+        self.strategies.retain(|strategy| strategy.name() != name);
+        self.strategies.len() != before
+    }
+
+    /// Position of the first strategy with the given name
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.iter().position(|strategy| strategy.name() == name)
+    }
+}
+
+impl<S: Strategy + 'static> FromIterator<S> for StrategyRegistry {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut registry = Self::new();
+        for strategy in iter {
+            registry.register(strategy);
+        }
+        registry
+    }
+}
+
+impl fmt::Debug for StrategyRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.iter().map(|strategy| strategy.name()))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SudokuPossibilities, strategy::StrategyResult};
+
+    struct Nothing;
+
+    impl Strategy for Nothing {
+        fn name(&self) -> &str {
+            "Nothing"
+        }
+
+        fn apply(&self, _: &mut SudokuPossibilities) -> StrategyResult {
+            StrategyResult::NoProgress
+        }
+    }
+
+    #[test]
+    fn edit_registry() {
+        let mut registry = StrategyRegistry::standard();
+        assert_eq!(registry.iter().next().unwrap().name(), "Hidden Single");
+        assert_eq!(registry.position("Jellyfish"), None);
+
+        let after = registry.position("Finned Swordfish").unwrap() + 1;
+        registry.insert(after, Technique::Jellyfish);
+        assert_eq!(registry.position("Jellyfish"), Some(after));
+
+        assert!(registry.remove("X-Wing"));
+        assert!(!registry.remove("X-Wing"));
+        registry.register(Nothing);
+        assert_eq!(registry.iter().last().unwrap().name(), "Nothing");
+        assert_eq!(
+            format!("{:?}", StrategyRegistry::basic()),
+            r#"["Hidden Single", "Pointing", "Box/Line Reduction"]"#
+        );
+    }
+
+    #[test]
+    fn extended_adds_jellyfish() {
+        let standard = StrategyRegistry::standard();
+        let mut extended = StrategyRegistry::extended();
+        assert_eq!(standard.position("Jellyfish"), None);
+        assert_eq!(
+            extended.position("Jellyfish"),
+            Some(standard.position("Finned Swordfish").unwrap() + 1)
+        );
+        assert!(extended.remove("Jellyfish"));
+        assert_eq!(format!("{extended:?}"), format!("{standard:?}"));
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{Digit, PossibleValues, SudokuPossibilities, Unit, Units};
+
+use super::{
+    eliminate_seen_by,
+    links::{StrongLink, strong_links},
+};
+
+/// Both ends of each link, in both orders
+fn ends(link: StrongLink) -> [((usize, usize), (usize, usize)); 2] {
+    [(link.0, link.1), (link.1, link.0)]
+}
+
+/// Skyscraper: two strong links on parallel lines whose base ends share a
+/// perpendicular line. One of the two roof ends must hold the digit, so it is
+/// removed from every cell that sees both of them.
+pub fn skyscraper(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    let mut progress = false;
+    for d in 0..9 {
+        let digit = Digit::from_index(d).unwrap();
+        let links = strong_links(sp, units, digit);
+        for transpose in [false, true] {
+            // Base line of a cell, and the position along it
+            let line = |cell: (usize, usize)| if transpose { cell.1 } else { cell.0 };
+            let along = |cell: (usize, usize)| if transpose { cell.0 } else { cell.1 };
+
+            let parallel: Vec<StrongLink> = links
+                .iter()
+                .copied()
+                .filter(|&(a, b)| line(a) == line(b))
+                .collect();
+            for (i, &l1) in parallel.iter().enumerate() {
+                for &l2 in &parallel[i + 1..] {
+                    if line(l1.0) == line(l2.0) {
+                        continue;
+                    }
+                    for (base1, roof1) in ends(l1) {
+                        for (base2, roof2) in ends(l2) {
+                            if along(base1) == along(base2) && along(roof1) != along(roof2) {
+                                progress |= eliminate_seen_by(
+                                    sp,
+                                    units,
+                                    &[roof1, roof2],
+                                    PossibleValues::from(digit),
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    progress
+}
+
+/// Two-string kite: a strong link in a row and one in a column whose base ends
+/// share a box. One of the two tails must hold the digit, so it is removed
+/// from every cell that sees both tails.
+pub fn two_string_kite(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    let mut progress = false;
+    for d in 0..9 {
+        let digit = Digit::from_index(d).unwrap();
+        let links = strong_links(sp, units, digit);
+        let rows = links.iter().filter(|(a, b)| a.0 == b.0);
+        for &row_link in rows {
+            let cols = links.iter().filter(|(a, b)| a.1 == b.1);
+            for &col_link in cols {
+                for (row_base, row_tail) in ends(row_link) {
+                    for (col_base, col_tail) in ends(col_link) {
+                        let cells = [row_base, row_tail, col_base, col_tail];
+                        let distinct = (0..4).all(|i| !cells[i + 1..].contains(&cells[i]));
+                        let same_box = units.region_of(row_base) == units.region_of(col_base);
+                        if distinct && same_box {
+                            progress |= eliminate_seen_by(
+                                sp,
+                                units,
+                                &[row_tail, col_tail],
+                                PossibleValues::from(digit),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+    progress
+}
+
+/// Empty rectangle: the candidates of a digit within a box all lie on one row
+/// and one column of the box. A strong link with one end on that row (column)
+/// forbids the digit where the link's other end meets the box's column (row).
+pub fn empty_rectangle(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    let mut progress = false;
+    for d in 0..9 {
+        let digit = Digit::from_index(d).unwrap();
+        let links = strong_links(sp, units, digit);
+        for b in 0..9 {
+            let cells = units.cells(Unit::Box(b));
+            let places: Vec<(usize, usize)> = cells
+                .into_iter()
+                .filter(|&(r, c)| sp.grid[r][c].contains(digit))
+                .collect();
+            if places.len() < 2 {
+                continue;
+            }
+            let mut rows = cells.map(|(r, _)| r).to_vec();
+            let mut cols = cells.map(|(_, c)| c).to_vec();
+            for lines in [&mut rows, &mut cols] {
+                lines.sort_unstable();
+                lines.dedup();
+            }
+            for &r in &rows {
+                for &c in &cols {
+                    if !places.iter().all(|&(pr, pc)| pr == r || pc == c) {
+                        continue;
+                    }
+                    for &link in &links {
+                        for (p, q) in ends(link) {
+                            if units.region_of(p) == b {
+                                continue;
+                            }
+                            // Where the row and column cross outside of a
+                            // jigsaw region, the target could be the other end
+                            let target = if p.1 == q.1 && p.0 == r && p.1 != c {
+                                (q.0, c)
+                            } else if p.0 == q.0 && p.1 == c && p.0 != r {
+                                (r, q.1)
+                            } else {
+                                continue;
+                            };
+                            if units.region_of(target) != b
+                                && sp.grid[target.0][target.1].contains(digit)
+                            {
+                                sp.grid[target.0][target.1].remove(digit);
+                                progress = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    progress
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skyscraper_in_rows() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let six = Digit::unchecked(6);
+        // Row 0: columns 1 and 4, row 5: columns 1 and 5
+        for c in 0..9 {
+            if c != 1 && c != 4 {
+                sp.grid[0][c].remove(six);
+            }
+            if c != 1 && c != 5 {
+                sp.grid[5][c].remove(six);
+            }
+        }
+        assert!(skyscraper(&mut sp, Units::classic()));
+        // Cells seeing both (0, 4) and (5, 5)
+        assert!(!sp.grid[1][5].contains(six));
+        assert!(!sp.grid[2][5].contains(six));
+        assert!(!sp.grid[4][4].contains(six));
+        assert!(!sp.grid[3][4].contains(six));
+        assert!(sp.grid[1][1].contains(six));
+        assert!(sp.grid[8][5].contains(six));
+        assert!(!skyscraper(&mut sp, Units::classic()));
+    }
+
+    #[test]
+    fn two_string_kite_in_box() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let two = Digit::unchecked(2);
+        // Row 1: columns 0 and 6, column 2: rows 0 and 7
+        for i in 0..9 {
+            if i != 0 && i != 6 {
+                sp.grid[1][i].remove(two);
+            }
+            if i != 0 && i != 7 {
+                sp.grid[i][2].remove(two);
+            }
+        }
+        assert!(two_string_kite(&mut sp, Units::classic()));
+        assert!(!sp.grid[7][6].contains(two));
+        assert!(sp.grid[7][5].contains(two));
+        assert!(sp.grid[6][6].contains(two));
+    }
+
+    #[test]
+    fn empty_rectangle_with_column_link() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let five = Digit::unchecked(5);
+        // In box 0, digit 5 lies on row 1 and column 1 only
+        for (r, c) in Unit::Box(0).cells() {
+            if (r, c) != (1, 0) && (r, c) != (0, 1) {
+                sp.grid[r][c].remove(five);
+            }
+        }
+        // Strong link in column 5 between rows 1 and 6
+        for r in 0..9 {
+            if r != 1 && r != 6 {
+                sp.grid[r][5].remove(five);
+            }
+        }
+        assert!(empty_rectangle(&mut sp, Units::classic()));
+        assert!(!sp.grid[6][1].contains(five));
+        assert!(sp.grid[6][0].contains(five));
+        assert!(sp.grid[7][1].contains(five));
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{Digit, PossibleValues, SudokuPossibilities, Unit, Units};
+
+/// Place a digit that has only one possible cell left in some unit.
+/// Returns true if any cell was changed.
+pub fn hidden_singles(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    let mut progress = false;
+    for unit in Unit::ALL {
+        for d in 0..9 {
+            let digit = Digit::from_index(d).unwrap();
+            let mut places = units
+                .cells(unit)
+                .into_iter()
+                .filter(|&(r, c)| sp.grid[r][c].contains(digit));
+            let (Some((r, c)), None) = (places.next(), places.next()) else {
+                continue;
+            };
+            if sp.grid[r][c].determined().is_none() {
+                sp.grid[r][c] = PossibleValues::from(digit);
+                progress = true;
+            }
+        }
+    }
+    progress
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hidden_single_in_row() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        let five = Digit::unchecked(5);
+        for c in 0..9 {
+            if c != 6 {
+                sp.grid[2][c].remove(five);
+            }
+        }
+        assert!(hidden_singles(&mut sp, Units::classic()));
+        assert_eq!(sp.grid[2][6].determined(), Some(five));
+        assert!(!hidden_singles(&mut sp, Units::classic()));
+    }
+}
@@ -0,0 +1,199 @@
+use crate::{Digit, PossibleValues, SudokuPossibilities, Unit, Units};
+
+use super::combinations;
+
+/// If `size` cells of a unit together have exactly `size` candidates,
+/// those digits must go in these cells and can be removed from the rest of the unit.
+/// Returns true if any candidates were removed.
+pub fn naked_subsets(sp: &mut SudokuPossibilities, units: &Units, size: usize) -> bool {
+    let mut progress = false;
+    for unit in Unit::ALL {
+        let cells = units.cells(unit);
+        let open: Vec<usize> = (0..9)
+            .filter(|&i| {
+                let (r, c) = cells[i];
+                (2..=size).contains(&(sp.grid[r][c].count() as usize))
+            })
+            .collect();
+
+        combinations(&open, size, &mut |subset| {
+            let mut digits = PossibleValues::EMPTY;
+            for &i in subset {
+                let (r, c) = cells[i];
+                digits |= sp.grid[r][c];
+            }
+            if digits.count() as usize != size {
+                return;
+            }
+            for (i, &(r, c)) in cells.iter().enumerate() {
+                if subset.contains(&i) {
+                    continue;
+                }
+                let before = sp.grid[r][c];
+                sp.grid[r][c] &= !digits;
+                progress |= sp.grid[r][c] != before;
+            }
+        });
+    }
+    progress
+}
+
+/// If `size` digits of a unit can only go in the same `size` cells,
+/// those cells cannot contain any other digit.
+/// Returns true if any candidates were removed.
+pub fn hidden_subsets(sp: &mut SudokuPossibilities, units: &Units, size: usize) -> bool {
+    let mut progress = false;
+    for unit in Unit::ALL {
+        let cells = units.cells(unit);
+        let positions = |digit: Digit| -> Vec<usize> {
+            (0..9)
+                .filter(|&i| {
+                    let (r, c) = cells[i];
+                    sp.grid[r][c].contains(digit)
+                })
+                .collect()
+        };
+        let open: Vec<Digit> = (0..9)
+            .map(|d| Digit::from_index(d).unwrap())
+            .filter(|&d| (2..=size).contains(&positions(d).len()))
+            .collect();
+
+        let mut found = Vec::new();
+        combinations(&open, size, &mut |subset| {
+            let mut places: Vec<usize> = subset.iter().flat_map(|&d| positions(d)).collect();
+            places.sort_unstable();
+            places.dedup();
+            if places.len() == size {
+                let digits = subset.iter().fold(PossibleValues::EMPTY, |acc, &d| {
+                    acc | PossibleValues::from(d)
+                });
+                found.push((places, digits));
+            }
+        });
+
+        for (places, digits) in found {
+            for i in places {
+                let (r, c) = cells[i];
+                let before = sp.grid[r][c];
+                sp.grid[r][c] &= digits;
+                progress |= sp.grid[r][c] != before;
+            }
+        }
+    }
+    progress
+}
+
+pub fn naked_pairs(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    naked_subsets(sp, units, 2)
+}
+
+pub fn naked_triples(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    naked_subsets(sp, units, 3)
+}
+
+pub fn naked_quads(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    naked_subsets(sp, units, 4)
+}
+
+pub fn hidden_pairs(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    hidden_subsets(sp, units, 2)
+}
+
+pub fn hidden_triples(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    hidden_subsets(sp, units, 3)
+}
+
+pub fn hidden_quads(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    hidden_subsets(sp, units, 4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(digits: &[u8]) -> PossibleValues {
+        let mut pv = PossibleValues::EMPTY;
+        for &d in digits {
+            pv.add(Digit::unchecked(d));
+        }
+        pv
+    }
+
+    #[test]
+    fn naked_pair_in_row() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.grid[0][3] = set(&[3, 7]);
+        sp.grid[0][5] = set(&[3, 7]);
+        assert!(naked_pairs(&mut sp, Units::classic()));
+        assert_eq!(sp.grid[0][0], !set(&[3, 7]));
+        assert_eq!(sp.grid[0][5], set(&[3, 7]));
+        // Box 1 also contains the pair
+        assert_eq!(sp.grid[1][4], !set(&[3, 7]));
+        assert!(!naked_pairs(&mut sp, Units::classic()));
+    }
+
+    #[test]
+    fn naked_triple_without_full_cells() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.grid[4][0] = set(&[1, 2]);
+        sp.grid[4][3] = set(&[2, 9]);
+        sp.grid[4][8] = set(&[1, 9]);
+        assert!(naked_triples(&mut sp, Units::classic()));
+        assert_eq!(sp.grid[4][4], !set(&[1, 2, 9]));
+        assert_eq!(sp.grid[4][3], set(&[2, 9]));
+    }
+
+    #[test]
+    fn hidden_pair_in_column() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        for r in 0..9 {
+            if r != 2 && r != 7 {
+                sp.grid[r][4] = !set(&[4, 6]);
+            }
+        }
+        assert!(hidden_pairs(&mut sp, Units::classic()));
+        assert_eq!(sp.grid[2][4], set(&[4, 6]));
+        assert_eq!(sp.grid[7][4], set(&[4, 6]));
+        assert!(!hidden_pairs(&mut sp, Units::classic()));
+    }
+
+    #[test]
+    fn hidden_triple_in_box() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        for (r, c) in Unit::Box(8).cells() {
+            if ![(6, 6), (7, 7), (8, 8)].contains(&(r, c)) {
+                sp.grid[r][c] = !set(&[1, 5, 8]);
+            }
+        }
+        sp.grid[6][6].remove(Digit::unchecked(8));
+        assert!(hidden_triples(&mut sp, Units::classic()));
+        assert_eq!(sp.grid[6][6], set(&[1, 5]));
+        assert_eq!(sp.grid[8][8], set(&[1, 5, 8]));
+    }
+
+    #[test]
+    fn naked_quad_in_row() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.grid[8][0] = set(&[1, 4]);
+        sp.grid[8][2] = set(&[4, 6, 9]);
+        sp.grid[8][4] = set(&[1, 6]);
+        sp.grid[8][7] = set(&[1, 6, 9]);
+        assert!(!naked_triples(&mut sp, Units::classic()));
+        assert!(naked_quads(&mut sp, Units::classic()));
+        assert_eq!(sp.grid[8][1], !set(&[1, 4, 6, 9]));
+        assert_eq!(sp.grid[8][8], !set(&[1, 4, 6, 9]));
+    }
+
+    #[test]
+    fn hidden_quad_in_row() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        for c in [1, 3, 4, 6, 8] {
+            sp.grid[0][c] = !set(&[2, 3, 5, 7]);
+        }
+        assert!(!hidden_triples(&mut sp, Units::classic()));
+        assert!(hidden_quads(&mut sp, Units::classic()));
+        for c in [0, 2, 5, 7] {
+            assert_eq!(sp.grid[0][c], set(&[2, 3, 5, 7]));
+        }
+    }
+}
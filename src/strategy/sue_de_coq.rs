@@ -0,0 +1,128 @@
+use crate::{PossibleValues, SudokuPossibilities, Units};
+
+use super::{combinations, intersections::box_line_pairs};
+
+/// Unsolved cells of `unit` that pass `filter`
+fn open_cells(
+    sp: &SudokuPossibilities,
+    cells: [(usize, usize); 9],
+    filter: impl Fn((usize, usize)) -> bool,
+) -> Vec<(usize, usize)> {
+    cells
+        .into_iter()
+        .filter(|&(r, c)| sp.grid[r][c].count() >= 2 && filter((r, c)))
+        .collect()
+}
+
+/// Every subset of `cells` together with the union of its candidates
+fn subsets(
+    sp: &SudokuPossibilities,
+    cells: &[(usize, usize)],
+) -> Vec<(Vec<(usize, usize)>, PossibleValues)> {
+    let mut result = Vec::new();
+    for size in 1..=cells.len() {
+        combinations(cells, size, &mut |subset| {
+            let digits = subset
+                .iter()
+                .fold(PossibleValues::EMPTY, |acc, &(r, c)| acc | sp.grid[r][c]);
+            result.push((subset.to_vec(), digits));
+        });
+    }
+    result
+}
+
+/// Sue de Coq: two or three cells where a line crosses a box hold at least
+/// two more candidates than cells. Together with some cells of the line and
+/// some cells of the box, whose candidates don't overlap, they have exactly
+/// as many candidates as cells. Every digit is then placed exactly once in
+/// these cells, so the line digits are removed from the rest of the line
+/// and the box digits from the rest of the box.
+pub fn sue_de_coq(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    let mut found = Vec::new();
+    for (bx, line) in box_line_pairs(units) {
+        let (box_cells, line_cells) = (units.cells(bx), units.cells(line));
+        let in_line = |cell| line_cells.contains(&cell);
+        let in_box = |cell| box_cells.contains(&cell);
+        let cross = open_cells(sp, line_cells, in_box);
+        // Jigsaw regions can meet a line in more cells than a box
+        if cross.len() > 3 {
+            continue;
+        }
+        let line_rest = subsets(sp, &open_cells(sp, line_cells, |cell| !in_box(cell)));
+        let box_rest = subsets(sp, &open_cells(sp, box_cells, |cell| !in_line(cell)));
+
+        for size in 2..=cross.len() {
+            combinations(&cross, size, &mut |core| {
+                let core_digits = core
+                    .iter()
+                    .fold(PossibleValues::EMPTY, |acc, &(r, c)| acc | sp.grid[r][c]);
+                if (core_digits.count() as usize) < size + 2 {
+                    return;
+                }
+                for (line_extra, line_digits) in &line_rest {
+                    for (box_extra, box_digits) in &box_rest {
+                        let cells = size + line_extra.len() + box_extra.len();
+                        let digits = core_digits | *line_digits | *box_digits;
+                        if *line_digits & *box_digits != PossibleValues::EMPTY
+                            || digits.count() as usize != cells
+                        {
+                            continue;
+                        }
+                        let mut locked = core.to_vec();
+                        locked.extend(line_extra);
+                        locked.extend(box_extra);
+                        found.push((
+                            line,
+                            locked.clone(),
+                            *line_digits | (core_digits & !*box_digits),
+                        ));
+                        found.push((bx, locked, *box_digits | (core_digits & !*line_digits)));
+                    }
+                }
+            });
+        }
+    }
+
+    let mut progress = false;
+    for (unit, locked, digits) in found {
+        for (r, c) in units.cells(unit) {
+            if locked.contains(&(r, c)) {
+                continue;
+            }
+            let before = sp.grid[r][c];
+            sp.grid[r][c] &= !digits;
+            progress |= sp.grid[r][c] != before;
+        }
+    }
+    progress
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Digit;
+
+    use super::*;
+
+    fn set(digits: &[u8]) -> PossibleValues {
+        digits.iter().fold(PossibleValues::EMPTY, |acc, &d| {
+            acc | PossibleValues::from(Digit::unchecked(d))
+        })
+    }
+
+    #[test]
+    fn sue_de_coq_row_and_box() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        // {1, 2, 3, 4} twice where row 0 meets box 0,
+        // with {1, 2} elsewhere in the row and {3, 4} elsewhere in the box
+        sp.grid[0][0] = set(&[1, 2, 3, 4]);
+        sp.grid[0][1] = set(&[1, 2, 3, 4]);
+        sp.grid[0][5] = set(&[1, 2]);
+        sp.grid[1][1] = set(&[3, 4]);
+        assert!(sue_de_coq(&mut sp, Units::classic()));
+        assert_eq!(sp.grid[0][2], set(&[5, 6, 7, 8, 9]));
+        assert_eq!(sp.grid[0][8], set(&[3, 4, 5, 6, 7, 8, 9]));
+        assert_eq!(sp.grid[2][2], set(&[1, 2, 5, 6, 7, 8, 9]));
+        assert_eq!(sp.grid[0][0], set(&[1, 2, 3, 4]));
+        assert_eq!(sp.grid[3][0], PossibleValues::ANY);
+    }
+}
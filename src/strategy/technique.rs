@@ -0,0 +1,155 @@
+use crate::{SudokuPossibilities, Units};
+
+use super::{Strategy, StrategyResult};
+
+/// The strategies built into the crate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Technique {
+    HiddenSingles,
+    Pointing,
+    BoxLineReduction,
+    NakedPairs,
+    NakedTriples,
+    HiddenPairs,
+    HiddenTriples,
+    NakedQuads,
+    HiddenQuads,
+    Skyscraper,
+    TwoStringKite,
+    EmptyRectangle,
+    XWing,
+    FinnedXWing,
+    Swordfish,
+    FinnedSwordfish,
+    Jellyfish,
+    XyWing,
+    XyzWing,
+    WWing,
+    SimpleColoring,
+    Medusa,
+    /// X-Chains of at most the given number of links
+    XChains(usize),
+    /// XY-Chains of at most the given number of links
+    XyChains(usize),
+    AlsXz,
+    SueDeCoq,
+    /// Forcing chains of at most the given number of placements
+    ForcingChains(usize),
+    UniqueRectangles,
+    BugPlusOne,
+}
+
+impl Strategy for Technique {
+    fn name(&self) -> &str {
+        match self {
+            Self::HiddenSingles => "Hidden Single",
+            Self::Pointing => "Pointing",
+            Self::BoxLineReduction => "Box/Line Reduction",
+            Self::NakedPairs => "Naked Pair",
+            Self::NakedTriples => "Naked Triple",
+            Self::HiddenPairs => "Hidden Pair",
+            Self::HiddenTriples => "Hidden Triple",
+            Self::NakedQuads => "Naked Quad",
+            Self::HiddenQuads => "Hidden Quad",
+            Self::Skyscraper => "Skyscraper",
+            Self::TwoStringKite => "2-String Kite",
+            Self::EmptyRectangle => "Empty Rectangle",
+            Self::XWing => "X-Wing",
+            Self::FinnedXWing => "Finned X-Wing",
+            Self::Swordfish => "Swordfish",
+            Self::FinnedSwordfish => "Finned Swordfish",
+            Self::Jellyfish => "Jellyfish",
+            Self::XyWing => "XY-Wing",
+            Self::XyzWing => "XYZ-Wing",
+            Self::WWing => "W-Wing",
+            Self::SimpleColoring => "Simple Coloring",
+            Self::Medusa => "3D Medusa",
+            Self::XChains(_) => "X-Chain",
+            Self::XyChains(_) => "XY-Chain",
+            Self::AlsXz => "ALS-XZ",
+            Self::SueDeCoq => "Sue de Coq",
+            Self::ForcingChains(_) => "Forcing Chain",
+            Self::UniqueRectangles => "Unique Rectangle",
+            Self::BugPlusOne => "BUG+1",
+        }
+    }
+
+    fn assumes_unique_solution(&self) -> bool {
+        matches!(self, Self::UniqueRectangles | Self::BugPlusOne)
+    }
+
+    fn difficulty(&self) -> u32 {
+        match self {
+            Self::HiddenSingles => 10,
+            Self::Pointing => 17,
+            Self::BoxLineReduction => 17,
+            Self::NakedPairs => 20,
+            Self::HiddenPairs => 22,
+            Self::NakedTriples => 24,
+            Self::HiddenTriples => 26,
+            Self::NakedQuads => 28,
+            Self::HiddenQuads => 29,
+            Self::XWing => 30,
+            Self::Skyscraper => 32,
+            Self::TwoStringKite => 32,
+            Self::EmptyRectangle => 35,
+            Self::FinnedXWing => 36,
+            Self::Swordfish => 38,
+            Self::FinnedSwordfish => 42,
+            Self::Jellyfish => 48,
+            Self::XyWing => 40,
+            Self::XyzWing => 44,
+            Self::WWing => 44,
+            Self::UniqueRectangles => 45,
+            Self::BugPlusOne => 46,
+            Self::SimpleColoring => 50,
+            Self::XChains(_) => 60,
+            Self::XyChains(_) => 62,
+            Self::Medusa => 65,
+            Self::AlsXz => 70,
+            Self::SueDeCoq => 72,
+            Self::ForcingChains(_) => 85,
+        }
+    }
+
+    fn apply(&self, sp: &mut SudokuPossibilities) -> StrategyResult {
+        self.apply_in(sp, Units::classic())
+    }
+
+    fn apply_in(&self, sp: &mut SudokuPossibilities, units: &Units) -> StrategyResult {
+        use super::*;
+
+        match *self {
+            Self::HiddenSingles => hidden_singles(sp, units),
+            Self::Pointing => pointing(sp, units),
+            Self::BoxLineReduction => box_line_reduction(sp, units),
+            Self::NakedPairs => naked_pairs(sp, units),
+            Self::NakedTriples => naked_triples(sp, units),
+            Self::HiddenPairs => hidden_pairs(sp, units),
+            Self::HiddenTriples => hidden_triples(sp, units),
+            Self::NakedQuads => naked_quads(sp, units),
+            Self::HiddenQuads => hidden_quads(sp, units),
+            Self::Skyscraper => skyscraper(sp, units),
+            Self::TwoStringKite => two_string_kite(sp, units),
+            Self::EmptyRectangle => empty_rectangle(sp, units),
+            Self::XWing => x_wing(sp),
+            Self::FinnedXWing => finned_x_wing(sp, units),
+            Self::Swordfish => swordfish(sp),
+            Self::FinnedSwordfish => finned_swordfish(sp, units),
+            Self::Jellyfish => jellyfish(sp),
+            Self::XyWing => xy_wing(sp, units),
+            Self::XyzWing => xyz_wing(sp, units),
+            Self::WWing => w_wing(sp, units),
+            Self::SimpleColoring => simple_coloring(sp, units),
+            Self::Medusa => medusa(sp, units),
+            Self::XChains(max_length) => x_chains(sp, units, max_length),
+            Self::XyChains(max_length) => xy_chains(sp, units, max_length),
+            Self::AlsXz => als_xz(sp, units),
+            Self::SueDeCoq => sue_de_coq(sp, units),
+            Self::ForcingChains(max_steps) => forcing_chains(sp, units, max_steps),
+            Self::UniqueRectangles => unique_rectangles(sp, units),
+            Self::BugPlusOne => bug_plus_one(sp, units),
+        }
+        .into()
+    }
+}
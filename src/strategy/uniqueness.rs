@@ -0,0 +1,272 @@
+//! Techniques that rely on the puzzle having a unique solution.
+//! They must only be used when that is known to hold.
+
+use crate::{Digit, PossibleValues, SudokuPossibilities, Unit, Units};
+
+use super::{combinations, eliminate_seen_by};
+
+/// Rectangles of cells spanning exactly two rows, two columns and two
+/// regions, with each region holding a side of the rectangle. Corners are
+/// ordered so that `[0, 1]` and `[2, 3]` share a row.
+fn rectangles(units: &Units) -> impl Iterator<Item = [(usize, usize); 4]> + '_ {
+    let pairs = || (0..9).flat_map(|a| (a + 1..9).map(move |b| (a, b)));
+    pairs()
+        .flat_map(move |(r1, r2)| {
+            pairs().map(move |(c1, c2)| [(r1, c1), (r1, c2), (r2, c1), (r2, c2)])
+        })
+        .filter(|corners| {
+            let [a, b, c, d] = corners.map(|cell| units.region_of(cell));
+            a != d && ((a == b && c == d) || (a == c && b == d))
+        })
+}
+
+/// Unique rectangle types 1 to 4.
+///
+/// If four cells of a rectangle could all be only `{a, b}`, the digits could
+/// be swapped to get a second solution. So at least one corner must take
+/// some other digit:
+/// 1. three corners are `{a, b}`: remove `a` and `b` from the fourth
+/// 2. the two roof corners have the same single extra digit: it's removed
+///    from cells seeing both roof corners
+/// 3. the extra digits of the roof act as one cell in a naked subset
+/// 4. `a` is confined to the roof in a unit: `b` is removed from the roof
+pub fn unique_rectangles(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    let mut progress = false;
+    for corners in rectangles(units) {
+        let common = corners
+            .iter()
+            .fold(PossibleValues::ANY, |acc, &(r, c)| acc & sp.grid[r][c]);
+        let digits = common.options();
+        for (i, &a) in digits.iter().enumerate() {
+            for &b in &digits[i + 1..] {
+                let ab = PossibleValues::from(a) | PossibleValues::from(b);
+                let is_floor = |(r, c): (usize, usize)| sp.grid[r][c] == ab;
+                let (floor, roof): (Vec<_>, Vec<_>) =
+                    corners.into_iter().partition(|&c| is_floor(c));
+                match (&floor[..], &roof[..]) {
+                    (_, &[target]) if floor.len() == 3 => {
+                        sp.grid[target.0][target.1] &= !ab;
+                        progress = true;
+                    }
+                    (&[f1, f2], &[r1, r2]) if f1.0 == f2.0 || f1.1 == f2.1 => {
+                        progress |= roof_eliminations(sp, units, ab, r1, r2);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    progress
+}
+
+/// Types 2 to 4, given the `{a, b}` pair and the two roof corners
+fn roof_eliminations(
+    sp: &mut SudokuPossibilities,
+    units: &Units,
+    ab: PossibleValues,
+    r1: (usize, usize),
+    r2: (usize, usize),
+) -> bool {
+    let mut progress = false;
+    let extra = (sp.grid[r1.0][r1.1] | sp.grid[r2.0][r2.1]) & !ab;
+
+    // Type 2
+    if sp.grid[r1.0][r1.1] == sp.grid[r2.0][r2.1] && extra.count() == 1 {
+        progress |= eliminate_seen_by(sp, units, &[r1, r2], extra);
+    }
+
+    let (r1_units, r2_units) = (units.of(r1), units.of(r2));
+    let common = r1_units.into_iter().filter(|unit| r2_units.contains(unit));
+    for unit in common.map(|unit| Unit::ALL[unit]) {
+        let others: Vec<(usize, usize)> = units
+            .cells(unit)
+            .into_iter()
+            .filter(|&cell| cell != r1 && cell != r2)
+            .collect();
+
+        // Type 4
+        for digit in ab {
+            if others.iter().all(|&(r, c)| !sp.grid[r][c].contains(digit)) {
+                let other = ab & !PossibleValues::from(digit);
+                for (r, c) in [r1, r2] {
+                    if sp.grid[r][c] & other != PossibleValues::EMPTY {
+                        sp.grid[r][c] &= !other;
+                        progress = true;
+                    }
+                }
+            }
+        }
+
+        // Type 3
+        let open: Vec<(usize, usize)> = others
+            .iter()
+            .copied()
+            .filter(|&(r, c)| sp.grid[r][c].count() >= 2)
+            .collect();
+        for size in 1..=3 {
+            let mut found = Vec::new();
+            combinations(&open, size, &mut |subset| {
+                let union = subset
+                    .iter()
+                    .fold(extra, |acc, &(r, c)| acc | sp.grid[r][c]);
+                if union.count() as usize == size + 1 {
+                    found.push((subset.to_vec(), union));
+                }
+            });
+            for (subset, union) in found {
+                for &(r, c) in &others {
+                    if !subset.contains(&(r, c)) && sp.grid[r][c] & union != PossibleValues::EMPTY {
+                        sp.grid[r][c] &= !union;
+                        progress = true;
+                    }
+                }
+            }
+        }
+    }
+    progress
+}
+
+/// Bivalue universal grave + 1: if every unsolved cell has two candidates
+/// except one cell with three, and removing one of those three would leave
+/// every digit exactly twice or not at all in each unit, that cell must take
+/// the removed digit. The remaining pattern would always allow two solutions.
+pub fn bug_plus_one(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    let mut triple = None;
+    for r in 0..9 {
+        for c in 0..9 {
+            match sp.grid[r][c].count() {
+                0..=2 => {}
+                3 if triple.is_none() => triple = Some((r, c)),
+                _ => return false,
+            }
+        }
+    }
+    let Some((r, c)) = triple else {
+        return false;
+    };
+
+    // The pattern argument only holds if no candidate clashes with a solved cell
+    let cells = || (0..81).map(|i| (i / 9, i % 9));
+    for (a, b) in cells().flat_map(|a| cells().map(move |b| (a, b))) {
+        let (open, solved) = (sp.grid[a.0][a.1], sp.grid[b.0][b.1]);
+        if let Some(digit) = solved.determined()
+            && open.count() > 1
+            && open.contains(digit)
+            && units.sees(a, b)
+        {
+            return false;
+        }
+    }
+
+    for digit in sp.grid[r][c] {
+        let mut grave = *sp;
+        grave.grid[r][c].remove(digit);
+        let is_grave = Unit::ALL.iter().all(|unit| {
+            (0..9).all(|d| {
+                let d = Digit::from_index(d).unwrap();
+                let count = units
+                    .cells(*unit)
+                    .into_iter()
+                    .filter(|&(r, c)| grave.grid[r][c].count() > 1 && grave.grid[r][c].contains(d))
+                    .count();
+                count == 0 || count == 2
+            })
+        });
+        if is_grave {
+            sp.grid[r][c] = PossibleValues::from(digit);
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Sudoku;
+
+    fn set(digits: &[u8]) -> PossibleValues {
+        digits.iter().fold(PossibleValues::EMPTY, |acc, &d| {
+            acc | PossibleValues::from(Digit::unchecked(d))
+        })
+    }
+
+    #[test]
+    fn rectangle_count() {
+        // 9 row pairs within a band times 27 column pairs across stacks,
+        // and the same transposed
+        assert_eq!(rectangles(Units::classic()).count(), 2 * 9 * 27);
+    }
+
+    #[test]
+    fn type_1() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.grid[0][0] = set(&[3, 8]);
+        sp.grid[0][4] = set(&[3, 8]);
+        sp.grid[2][0] = set(&[3, 8]);
+        sp.grid[2][4] = set(&[1, 3, 8]);
+        assert!(unique_rectangles(&mut sp, Units::classic()));
+        assert_eq!(sp.grid[2][4], set(&[1]));
+    }
+
+    #[test]
+    fn type_2() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.grid[1][1] = set(&[4, 6]);
+        sp.grid[1][7] = set(&[4, 6]);
+        sp.grid[2][1] = set(&[4, 6, 9]);
+        sp.grid[2][7] = set(&[4, 6, 9]);
+        assert!(unique_rectangles(&mut sp, Units::classic()));
+        let nine = Digit::unchecked(9);
+        assert!(!sp.grid[2][4].contains(nine));
+        assert!(sp.grid[0][4].contains(nine));
+    }
+
+    #[test]
+    fn type_4() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.grid[3][2] = set(&[2, 5]);
+        sp.grid[5][2] = set(&[2, 5]);
+        sp.grid[3][5] = set(&[2, 5, 7]);
+        sp.grid[5][5] = set(&[2, 5, 8]);
+        // 2 is confined to the roof in column 5
+        let two = Digit::unchecked(2);
+        for r in 0..9 {
+            if r != 3 && r != 5 {
+                sp.grid[r][5].remove(two);
+            }
+        }
+        assert!(unique_rectangles(&mut sp, Units::classic()));
+        assert_eq!(sp.grid[3][5], set(&[2, 7]));
+        assert_eq!(sp.grid[5][5], set(&[2, 8]));
+    }
+
+    #[test]
+    fn bug_plus_one() {
+        // A solved grid with a bivalue pattern opened up, where 1 in (5, 6)
+        // is the only extra candidate
+        let sudoku = Sudoku::parse(
+            "462853971\n817492635\n359671482\n74.928.63\n926315847\n\
+             .38764..9\n.84.37.96\n293146758\n67..893.4",
+        )
+        .unwrap();
+        let mut sp = SudokuPossibilities::from(sudoku);
+        sp.propagate().unwrap();
+        assert_eq!(sp.grid[5][6], set(&[1, 2, 5]));
+
+        // A solved 5 in the row clashes with the candidates around it
+        let mut stale = sp;
+        stale.grid[5][1] = set(&[5]);
+        assert!(!super::bug_plus_one(&mut stale, Units::classic()));
+
+        let before = sp;
+        assert!(super::bug_plus_one(&mut sp, Units::classic()));
+        assert_eq!(sp.grid[5][6], set(&[1]));
+        assert_eq!(
+            sudoku.solve().unwrap().grid().grid[5][6],
+            Digit::unchecked(1)
+        );
+        sp.grid[5][6] = before.grid[5][6];
+        assert_eq!(sp, before);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{PossibleValues, SudokuPossibilities, Units};
+
+use super::{eliminate_seen_by, links::strong_links};
+
+/// Coordinates of all cells with exactly `count` candidates
+fn cells_with_count(sp: &SudokuPossibilities, count: u8) -> Vec<(usize, usize)> {
+    (0..81)
+        .map(|i| (i / 9, i % 9))
+        .filter(|&(r, c)| sp.grid[r][c].count() == count)
+        .collect()
+}
+
+/// XY-Wing: a bivalue pivot `{x, y}` sees two bivalue pincers `{x, z}` and `{y, z}`.
+/// Whichever value the pivot takes, one of the pincers is `z`,
+/// so `z` can be removed from every cell that sees both pincers.
+pub fn xy_wing(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    let bivalue = cells_with_count(sp, 2);
+    let mut progress = false;
+    for &pivot in &bivalue {
+        for &a in &bivalue {
+            for &b in &bivalue {
+                if a >= b || !units.sees(pivot, a) || !units.sees(pivot, b) {
+                    continue;
+                }
+                let (p, pa, pb) = (
+                    sp.grid[pivot.0][pivot.1],
+                    sp.grid[a.0][a.1],
+                    sp.grid[b.0][b.1],
+                );
+                let z = pa & !p;
+                if z.count() != 1 || z != pb & !p || (pa & p) == (pb & p) || (pa & p).count() != 1 {
+                    continue;
+                }
+                progress |= eliminate_seen_by(sp, units, &[a, b], z);
+            }
+        }
+    }
+    progress
+}
+
+/// XYZ-Wing: a trivalue pivot `{x, y, z}` sees two bivalue pincers `{x, z}` and `{y, z}`.
+/// One of the three cells must be `z`, so it can be removed from every cell
+/// that sees all of them.
+pub fn xyz_wing(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    let bivalue = cells_with_count(sp, 2);
+    let mut progress = false;
+    for pivot in cells_with_count(sp, 3) {
+        for &a in &bivalue {
+            for &b in &bivalue {
+                if a >= b || !units.sees(pivot, a) || !units.sees(pivot, b) {
+                    continue;
+                }
+                let (p, pa, pb) = (
+                    sp.grid[pivot.0][pivot.1],
+                    sp.grid[a.0][a.1],
+                    sp.grid[b.0][b.1],
+                );
+                let z = pa & pb;
+                if pa == pb || (pa | pb) != p || z.count() != 1 {
+                    continue;
+                }
+                progress |= eliminate_seen_by(sp, units, &[pivot, a, b], z);
+            }
+        }
+    }
+    progress
+}
+
+/// W-Wing: two identical bivalue cells `{x, y}` that don't see each other,
+/// connected by a strong link on `x` whose ends see one cell each.
+/// One of the two cells must then be `y`, so `y` is removed from their common peers.
+pub fn w_wing(sp: &mut SudokuPossibilities, units: &Units) -> bool {
+    let bivalue = cells_with_count(sp, 2);
+    let mut progress = false;
+    for &a in &bivalue {
+        for &b in &bivalue {
+            let pa = sp.grid[a.0][a.1];
+            if a >= b || units.sees(a, b) || pa != sp.grid[b.0][b.1] {
+                continue;
+            }
+            for x in pa {
+                let y = pa & !PossibleValues::from(x);
+                let linked = strong_links(sp, units, x).into_iter().any(|(l1, l2)| {
+                    let ends = [l1, l2];
+                    if ends.contains(&a) || ends.contains(&b) {
+                        return false;
+                    }
+                    (units.sees(l1, a) && units.sees(l2, b))
+                        || (units.sees(l1, b) && units.sees(l2, a))
+                });
+                if linked {
+                    progress |= eliminate_seen_by(sp, units, &[a, b], y);
+                }
+            }
+        }
+    }
+    progress
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Digit;
+
+    use super::*;
+
+    fn pair(a: u8, b: u8) -> PossibleValues {
+        PossibleValues::from(Digit::unchecked(a)) | PossibleValues::from(Digit::unchecked(b))
+    }
+
+    #[test]
+    fn xy_wing_eliminates_shared_peers() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.grid[0][0] = pair(1, 2); // pivot
+        sp.grid[0][6] = pair(1, 3); // pincer in row
+        sp.grid[2][1] = pair(2, 3); // pincer in box
+        assert!(xy_wing(&mut sp, Units::classic()));
+        let three = Digit::unchecked(3);
+        // Cells that see both pincers
+        assert!(!sp.grid[2][6].contains(three));
+        assert!(!sp.grid[0][1].contains(three));
+        assert!(!sp.grid[0][2].contains(three));
+        // Not seen by both
+        assert!(sp.grid[2][5].contains(three));
+        assert!(sp.grid[0][6].contains(three));
+        assert!(!xy_wing(&mut sp, Units::classic()));
+    }
+
+    #[test]
+    fn xyz_wing_eliminates_from_pivot_box() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.grid[0][0] = pair(1, 2) | pair(3, 3); // pivot
+        sp.grid[0][7] = pair(1, 3); // pincer in row
+        sp.grid[1][2] = pair(2, 3); // pincer in box
+        assert!(xyz_wing(&mut sp, Units::classic()));
+        let three = Digit::unchecked(3);
+        assert!(!sp.grid[0][1].contains(three));
+        assert!(!sp.grid[0][2].contains(three));
+        // Sees both pincers but not the pivot
+        assert!(sp.grid[1][7].contains(three));
+        assert!(sp.grid[0][0].contains(three));
+    }
+
+    #[test]
+    fn w_wing_with_row_link() {
+        let mut sp = SudokuPossibilities::EMPTY;
+        sp.grid[1][0] = pair(4, 7);
+        sp.grid[5][8] = pair(4, 7);
+        // Strong link on 4 in row 3: (3, 0) sees (1, 0) and (3, 8) sees (5, 8)
+        let four = Digit::unchecked(4);
+        for c in 1..8 {
+            sp.grid[3][c].remove(four);
+        }
+        assert!(w_wing(&mut sp, Units::classic()));
+        let seven = Digit::unchecked(7);
+        assert!(!sp.grid[1][8].contains(seven));
+        assert!(!sp.grid[5][0].contains(seven));
+        assert!(sp.grid[1][0].contains(seven));
+        assert!(sp.grid[2][8].contains(seven));
+    }
+}
@@ -0,0 +1,208 @@
+use crate::Bitboard;
+
+/// A row, column or box of the grid.
+/// Each unit must contain every digit exactly once. Where [`Units`] replace
+/// the boxes with other regions, `Box(i)` is region `i`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Row(usize),
+    Col(usize),
+    Box(usize),
+}
+
+impl Unit {
+    /// All 27 units: rows first, then columns, then boxes
+    pub const ALL: [Self; 27] = {
+        let mut all = [Self::Row(0); 27];
+        let mut i = 0;
+        while i < 9 {
+            all[i] = Self::Row(i);
+            all[9 + i] = Self::Col(i);
+            all[18 + i] = Self::Box(i);
+            i += 1;
+        }
+        all
+    };
+
+    /// Position in [`Unit::ALL`]
+    pub const fn index(self) -> usize {
+        match self {
+            Self::Row(r) => r,
+            Self::Col(c) => 9 + c,
+            Self::Box(b) => 18 + b,
+        }
+    }
+
+    /// Coordinates `(row, col)` of the cells in this unit
+    pub fn cells(self) -> [(usize, usize); 9] {
+        CLASSIC.cells(self)
+    }
+}
+
+/// Most peers a cell can have: 8 in each of its row, column and region
+const MAX_PEERS: usize = 24;
+
+/// Cells of the units and peers of each cell, for a grid whose boxes may be
+/// replaced with other regions of nine cells, as by a
+/// [`Jigsaw`](crate::constraint::Jigsaw). The strategies take them to find
+/// the regions of the puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Units {
+    /// Cells of each unit, in the order of [`Unit::ALL`], with the regions
+    /// in place of the boxes
+    cells: [[(usize, usize); 9]; 27],
+    /// Indices of the row, column and region of each cell
+    of: [[usize; 3]; 81],
+    peers: [[(usize, usize); MAX_PEERS]; 81],
+    peer_counts: [usize; 81],
+}
+
+/// Units of classic sudoku
+pub(crate) static CLASSIC: Units = Units::new(&{
+    let mut boxes = [[0; 9]; 9];
+    let mut i = 0;
+    while i < 81 {
+        boxes[i / 9][i % 9] = box_of((i / 9, i % 9));
+        i += 1;
+    }
+    boxes
+});
+
+impl Units {
+    /// Units with the region of each cell given by `regions`, each of
+    /// which must have exactly nine cells
+    pub(crate) const fn new(regions: &[[usize; 9]; 9]) -> Self {
+        let mut cells = [[(0, 0); 9]; 27];
+        let mut of = [[0; 3]; 81];
+        let mut filled = [0; 9];
+        let mut i = 0;
+        while i < 81 {
+            let (r, c) = (i / 9, i % 9);
+            let region = regions[r][c];
+            cells[r][c] = (r, c);
+            cells[9 + c][r] = (r, c);
+            cells[18 + region][filled[region]] = (r, c);
+            filled[region] += 1;
+            of[i] = [r, 9 + c, 18 + region];
+            i += 1;
+        }
+
+        let mut peers = [[(0, 0); MAX_PEERS]; 81];
+        let mut peer_counts = [0; 81];
+        let mut i = 0;
+        while i < 81 {
+            let mut other = 0;
+            while other < 81 {
+                let shares_unit = of[i][0] == of[other][0]
+                    || of[i][1] == of[other][1]
+                    || of[i][2] == of[other][2];
+                if other != i && shares_unit {
+                    peers[i][peer_counts[i]] = (other / 9, other % 9);
+                    peer_counts[i] += 1;
+                }
+                other += 1;
+            }
+            i += 1;
+        }
+        Self {
+            cells,
+            of,
+            peers,
+            peer_counts,
+        }
+    }
+
+    /// Rows, columns and boxes of classic sudoku
+    pub fn classic() -> &'static Self {
+        &CLASSIC
+    }
+
+    /// Whether the regions are the boxes of classic sudoku
+    pub fn is_classic(&self) -> bool {
+        self.cells[18..] == CLASSIC.cells[18..]
+    }
+
+    /// Coordinates `(row, col)` of the cells of a unit
+    pub fn cells(&self, unit: Unit) -> [(usize, usize); 9] {
+        self.cells[unit.index()]
+    }
+
+    /// The cells of a unit as a [`Bitboard`]
+    pub fn board(&self, unit: Unit) -> Bitboard {
+        self.cells(unit)
+            .into_iter()
+            .fold(Bitboard::EMPTY, |board, cell| board | Bitboard::cell(cell))
+    }
+
+    /// The peers of a cell as a [`Bitboard`]
+    pub fn peer_board(&self, cell: (usize, usize)) -> Bitboard {
+        let [row, col, region] = self.of(cell);
+        let units = [row, col, region].map(|u| self.board(Unit::ALL[u]));
+        (units[0] | units[1] | units[2]) & !Bitboard::cell(cell)
+    }
+
+    /// Index of the region containing the given cell
+    pub fn region_of(&self, cell: (usize, usize)) -> usize {
+        self.of(cell)[2] - 18
+    }
+
+    /// Whether two distinct cells share a unit
+    pub fn sees(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        let (a_units, b_units) = (self.of(a), self.of(b));
+        a != b && (0..3).any(|i| a_units[i] == b_units[i])
+    }
+
+    /// Cells that share a unit with the given cell
+    pub fn peers(&self, (row, col): (usize, usize)) -> &[(usize, usize)] {
+        &self.peers[row * 9 + col][..self.peer_counts[row * 9 + col]]
+    }
+
+    /// Indices in [`Unit::ALL`] of the row, column and region containing
+    /// the given cell
+    pub(crate) fn of(&self, (row, col): (usize, usize)) -> [usize; 3] {
+        self.of[row * 9 + col]
+    }
+}
+
+/// Index of the box containing the given cell
+pub const fn box_of((row, col): (usize, usize)) -> usize {
+    (row / 3) * 3 + col / 3
+}
+
+/// The 20 cells that share a unit with the given cell in classic sudoku
+pub fn peers(cell: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+    CLASSIC.peers(cell).iter().copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_cells() {
+        for b in 0..9 {
+            for (r, c) in Unit::Box(b).cells() {
+                assert_eq!(box_of((r, c)), b);
+            }
+        }
+        assert_eq!(Unit::Box(5).cells()[0], (3, 6));
+    }
+
+    #[test]
+    fn sees_peers() {
+        let units = Units::classic();
+        assert!(units.sees((0, 0), (0, 8)));
+        assert!(units.sees((0, 0), (8, 0)));
+        assert!(units.sees((0, 0), (2, 2)));
+        assert!(!units.sees((0, 0), (3, 3)));
+        assert!(!units.sees((4, 4), (4, 4)));
+        assert_eq!(peers((4, 4)).count(), 20);
+        assert!(peers((4, 4)).all(|p| units.sees(p, (4, 4))));
+        for unit in Unit::ALL {
+            assert_eq!(Unit::ALL[unit.index()], unit);
+            assert_eq!(units.board(unit), Bitboard::unit(unit));
+        }
+        assert_eq!(units.region_of((4, 7)), 5);
+        assert!(units.is_classic());
+    }
+}